@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::msg::Cw2981QueryMsg;
 use cosmwasm_std::Empty;
 use cw721_base::Cw721Contract;
-pub use cw721_base::{ContractError, InstantiateMsg, MintMsg, MinterResponse};
+pub use cw721_base::{ContractError, InstantiateMsg, MigrateMsg, MintMsg, MinterResponse};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct Trait {
@@ -52,7 +52,7 @@ pub mod entry {
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
     #[entry_point]
     pub fn instantiate(
@@ -74,6 +74,16 @@ pub mod entry {
         Cw2981Contract::default().execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        Cw2981Contract::default().migrate(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        Cw2981Contract::default().reply(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: Cw2981QueryMsg) -> StdResult<Binary> {
         match msg {
@@ -109,12 +119,20 @@ mod tests {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
             minter: CREATOR.to_string(),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: None,
+            max_supply: None,
+            governance: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         let token_id = "Enterprise";
         let mint_msg = MintMsg {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(Metadata {
@@ -122,6 +140,8 @@ mod tests {
                 name: Some("Starship USS Enterprise".to_string()),
                 ..Metadata::default()
             }),
+            non_transferable: None,
+            lock_until: None,
         };
         let exec_msg = ExecuteMsg::Mint(mint_msg.clone());
         entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
@@ -141,12 +161,20 @@ mod tests {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
             minter: CREATOR.to_string(),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: None,
+            max_supply: None,
+            governance: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         let token_id = "Enterprise";
         let mint_msg = MintMsg {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(Metadata {
@@ -154,6 +182,8 @@ mod tests {
                 name: Some("Starship USS Enterprise".to_string()),
                 ..Metadata::default()
             }),
+            non_transferable: None,
+            lock_until: None,
         };
         let exec_msg = ExecuteMsg::Mint(mint_msg);
         entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
@@ -180,12 +210,20 @@ mod tests {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
             minter: CREATOR.to_string(),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: None,
+            max_supply: None,
+            governance: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
         let token_id = "Enterprise";
         let mint_msg = MintMsg {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "JeanLuc".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(Metadata {
@@ -195,6 +233,8 @@ mod tests {
                 royalty_percentage: Some(10),
                 ..Metadata::default()
             }),
+            non_transferable: None,
+            lock_until: None,
         };
         let exec_msg = ExecuteMsg::Mint(mint_msg.clone());
         entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
@@ -220,7 +260,7 @@ mod tests {
         // which is the default behaviour
         let voyager_token_id = "Voyager";
         let second_mint_msg = MintMsg {
-            token_id: voyager_token_id.to_string(),
+            token_id: Some(voyager_token_id.to_string()),
             owner: "Janeway".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Voyager.json".into()),
             extension: Some(Metadata {
@@ -230,6 +270,8 @@ mod tests {
                 royalty_percentage: Some(4),
                 ..Metadata::default()
             }),
+            non_transferable: None,
+            lock_until: None,
         };
         let voyager_exec_msg = ExecuteMsg::Mint(second_mint_msg.clone());
         entry::execute(deps.as_mut(), mock_env(), info, voyager_exec_msg).unwrap();
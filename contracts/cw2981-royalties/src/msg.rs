@@ -118,11 +118,13 @@ impl From<Cw2981QueryMsg> for CW721QueryMsg {
                 start_after,
                 limit,
                 page: Some(0),
+                order: None,
             },
             Cw2981QueryMsg::AllTokens { start_after, limit } => CW721QueryMsg::AllTokens {
                 start_after,
                 limit,
                 page: Some(0),
+                order: None,
             },
             _ => panic!("cannot covert {:?} to CW721QueryMsg", msg),
         }
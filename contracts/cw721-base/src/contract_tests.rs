@@ -1,17 +1,38 @@
 #![cfg(test)]
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{from_binary, to_binary, CosmosMsg, DepsMut, Empty, Response, WasmMsg};
+use std::marker::PhantomData;
 
+use serde::Deserialize;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    attr, coin, coins, from_binary, from_slice, to_binary, to_vec, Addr, BankMsg, Binary,
+    ContractResult, CosmosMsg, Deps, DepsMut, Empty, Env, Event, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, Reply, Response, SubMsg, SubMsgExecutionResponse, SystemResult, WasmMsg,
+    WasmQuery,
+};
+
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+
+use cw2::{get_contract_version, set_contract_version};
 use cw721::{
-    Approval, ApprovalResponse, ContractInfoResponse, Cw721Query, Cw721ReceiveMsg, Expiration,
-    NftInfoResponse, OperatorsResponse, OwnerOfResponse,
+    Approval, ApprovalResponse, CheckTransferResponse, ContractInfoResponse, Cw721Event,
+    Cw721HookMsg, Cw721Query, Cw721ReceiveMsg, Cw721TransferCheck, Expiration, NftInfoResponse,
+    OperatorsResponse, OwnerOfResponse, TokensResponse, CW721_EVENT_VERSION,
 };
 
+use crate::execute::signer_address;
 use crate::{
-    ContractError, Cw721Contract, ExecuteMsg, Extension, InstantiateMsg, MintMsg, QueryMsg,
+    ContractError, Cw721Contract, ExecuteMsg, Extension, InstantiateMsg, MigrateMsg, MintMsg,
+    MintOrder, MintResponse, MinterRole, Operation, OperatorGrant, OwnerBalance,
+    PauseStatusResponse, Permit, QueryMsg, SoulboundResponse, Subscriber, SupplyInfoResponse,
+    TokenIdRules, TokenIdRulesResponse, TokenLock, TokensAtHeightResponse, Voucher,
 };
 
 const MINTER: &str = "merlin";
+const ADMIN: &str = "arthur";
 const CONTRACT_NAME: &str = "Magic Power";
 const SYMBOL: &str = "MGK";
 
@@ -21,6 +42,14 @@ fn setup_contract(deps: DepsMut<'_>) -> Cw721Contract<'static, Extension, Empty>
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         minter: String::from(MINTER),
+        admin: Some(String::from(ADMIN)),
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: None,
+        governance: None,
     };
     let info = mock_info("creator", &[]);
     let res = contract.instantiate(deps, mock_env(), info, msg).unwrap();
@@ -37,6 +66,14 @@ fn proper_instantiation() {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         minter: String::from(MINTER),
+        admin: Some(String::from(ADMIN)),
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: None,
+        governance: None,
     };
     let info = mock_info("creator", &[]);
 
@@ -49,6 +86,8 @@ fn proper_instantiation() {
     // it worked, let's query the state
     let res = contract.minter(deps.as_ref()).unwrap();
     assert_eq!(MINTER, res.minter);
+    let res = contract.admin(deps.as_ref()).unwrap();
+    assert_eq!(Some(ADMIN.to_string()), res.admin);
     let info = contract.contract_info(deps.as_ref()).unwrap();
     assert_eq!(
         info,
@@ -77,10 +116,12 @@ fn minting() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     // random cannot mint
@@ -129,10 +170,12 @@ fn minting() {
 
     // Cannot mint same token_id again
     let mint_msg2 = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     let allowed = mock_info(MINTER, &[]);
@@ -158,13 +201,17 @@ fn burning() {
     let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: MINTER.to_string(),
-        token_uri: Some(token_uri),
+        token_uri: Some(token_uri.clone()),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
-    let burn_msg = ExecuteMsg::Burn { token_id };
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
 
     // mint some NFT
     let allowed = mock_info(MINTER, &[]);
@@ -180,9 +227,13 @@ fn burning() {
 
     assert_eq!(err, ContractError::Unauthorized {});
 
-    let _ = contract
+    let res = contract
         .execute(deps.as_mut(), mock_env(), allowed, burn_msg)
         .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Cw721Event::burn(MINTER, MINTER, token_id, Some(token_uri)).into()]
+    );
 
     // ensure num tokens decreases
     let count = contract.num_tokens(deps.as_ref()).unwrap();
@@ -210,10 +261,12 @@ fn transferring_nft() {
     let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("venus"),
-        token_uri: Some(token_uri),
+        token_uri: Some(token_uri.clone()),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -247,6 +300,15 @@ fn transferring_nft() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                Event::new("cw721_transfer")
+                    .add_attribute("version", CW721_EVENT_VERSION)
+                    .add_attribute("sender", "venus")
+                    .add_attribute("from", "venus")
+                    .add_attribute("to", "random")
+                    .add_attribute("token_id", token_id.clone())
+                    .add_attribute("token_uri", token_uri)
+            )
             .add_attribute("action", "transfer_nft")
             .add_attribute("sender", "venus")
             .add_attribute("recipient", "random")
@@ -264,10 +326,12 @@ fn sending_nft() {
     let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("venus"),
         token_uri: Some(token_uri),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -313,6 +377,16 @@ fn sending_nft() {
         res,
         Response::new()
             .add_message(expected)
+            .add_event(
+                Cw721Event::send(
+                    "venus",
+                    "venus",
+                    "another_contract",
+                    &token_id,
+                    Some("https://www.merriam-webster.com/dictionary/melt".to_string())
+                )
+                .into()
+            )
             .add_attribute("action", "send_nft")
             .add_attribute("sender", "venus")
             .add_attribute("recipient", "another_contract")
@@ -330,10 +404,12 @@ fn approving_revoking() {
     let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id.clone(),
+        token_id: Some(token_id.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -374,6 +450,16 @@ fn approving_revoking() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(
+                Cw721Event::approve(
+                    "demeter",
+                    "demeter",
+                    "random",
+                    &token_id,
+                    Some("https://www.merriam-webster.com/dictionary/grow".to_string())
+                )
+                .into()
+            )
             .add_attribute("action", "approve")
             .add_attribute("sender", "demeter")
             .add_attribute("spender", "random")
@@ -477,10 +563,12 @@ fn approving_all_revoking_all() {
     let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
 
     let mint_msg1 = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -489,10 +577,12 @@ fn approving_all_revoking_all() {
         .unwrap();
 
     let mint_msg2 = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
 
     contract
@@ -523,6 +613,7 @@ fn approving_all_revoking_all() {
     assert_eq!(
         res,
         Response::new()
+            .add_event(Cw721Event::approve_all("demeter", "random").into())
             .add_attribute("action", "approve_all")
             .add_attribute("sender", "demeter")
             .add_attribute("operator", "random")
@@ -700,30 +791,36 @@ fn query_tokens_by_owner() {
     let token_id3 = "sing".to_string();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id1.clone(),
+        token_id: Some(token_id1.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
         .unwrap();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id2.clone(),
+        token_id: Some(token_id2.clone()),
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
         .unwrap();
 
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: token_id3.clone(),
+        token_id: Some(token_id3.clone()),
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        non_transferable: None,
+        lock_until: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter, mint_msg)
@@ -805,24 +902,2665 @@ fn query_by_pages() {
     // assert_eq!(vec!["41", "42"], tokens.tokens);
 }
 
-// helper functions for testing
-fn mint(
-    contract: &Cw721Contract<'static, Extension, Empty>,
-    deps: DepsMut<'_>,
-    token_id: String,
-    owner: String,
-) {
-    let token_uri = format!("{}{}", "https://lunapunks.io/", token_id);
+#[test]
+fn non_transferable_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER, &[]);
+    let owner = mock_info("demeter", &[]);
 
+    let token_id = "badge".to_string();
     let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id,
-        owner,
-        token_uri: Some(token_uri),
+        token_id: Some(token_id.clone()),
+        owner: String::from("demeter"),
+        token_uri: None,
         extension: None,
+        non_transferable: Some(true),
+        lock_until: None,
     });
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+        .unwrap();
+    mint(
+        &contract,
+        deps.as_mut(),
+        "coin".to_string(),
+        "demeter".to_string(),
+    );
 
-    let allowed = mock_info(MINTER, &[]);
-    let _ = contract
-        .execute(deps, mock_env(), allowed, mint_msg)
+    // only the soulbound token is reported as such
+    let res = contract
+        .transferable(deps.as_ref(), token_id.clone())
+        .unwrap();
+    assert!(!res.transferable);
+    let res = contract.transferable(deps.as_ref(), "coin".into()).unwrap();
+    assert!(res.transferable);
+
+    // owner can neither transfer, send nor approve it
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("ceres"),
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), transfer_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+    let send_msg = ExecuteMsg::SendNft {
+        contract: String::from("another_contract"),
+        token_id: token_id.clone(),
+        msg: to_binary("hello").unwrap(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), send_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+    let approve_msg = ExecuteMsg::Approve {
+        spender: String::from("ceres"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+
+    // operators of the owner can still move the other tokens, but not burn the soulbound one
+    let approve_all_msg = ExecuteMsg::ApproveAll {
+        operator: String::from("ceres"),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_all_msg)
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
+    let operator = mock_info("ceres", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), operator, burn_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // collection is not revocable, so the minter cannot burn it either
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter, burn_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // but the owner can
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, burn_msg)
+        .unwrap();
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!(1, count.count);
+}
+
+#[test]
+fn revocable_soulbound_collection() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        admin: None,
+        non_transferable: Some(true),
+        revocable: Some(true),
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: None,
+        governance: None,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+
+    let res = contract.soulbound(deps.as_ref()).unwrap();
+    assert_eq!(
+        res,
+        SoulboundResponse {
+            non_transferable: true,
+            revocable: true,
+        }
+    );
+
+    let token_id = "diploma".to_string();
+    mint(
+        &contract,
+        deps.as_mut(),
+        token_id.clone(),
+        "demeter".to_string(),
+    );
+    let res = contract
+        .transferable(deps.as_ref(), token_id.clone())
+        .unwrap();
+    assert!(!res.transferable);
+
+    // no operators in a soulbound collection
+    let owner = mock_info("demeter", &[]);
+    let approve_all_msg = ExecuteMsg::ApproveAll {
+        operator: String::from("ceres"),
+        expires: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner, approve_all_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NonTransferable {});
+
+    // random cannot revoke, the issuer can
+    let burn_msg = ExecuteMsg::Burn { token_id };
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), random, burn_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let minter = mock_info(MINTER, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, burn_msg)
+        .unwrap();
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!(0, count.count);
+}
+
+#[test]
+fn pausing() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    mint(
+        &contract,
+        deps.as_mut(),
+        "grow".to_string(),
+        "demeter".to_string(),
+    );
+
+    // only the admin can pause, not even the minter
+    let pause_msg = ExecuteMsg::Pause {
+        operations: Some(vec![Operation::Transfer]),
+    };
+    let minter = mock_info(MINTER, &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter, pause_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), pause_msg)
+        .unwrap();
+    let res = contract.pause_status(deps.as_ref()).unwrap();
+    assert_eq!(
+        res,
+        PauseStatusResponse {
+            mint: false,
+            transfer: true,
+            approve: false,
+            burn: false,
+        }
+    );
+
+    // transfers are halted, other operations are not
+    let owner = mock_info("demeter", &[]);
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("ceres"),
+        token_id: "grow".to_string(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: Operation::Transfer
+        }
+    );
+    mint(
+        &contract,
+        deps.as_mut(),
+        "sing".to_string(),
+        "demeter".to_string(),
+    );
+
+    // pause everything
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            admin.clone(),
+            ExecuteMsg::Pause { operations: None },
+        )
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "sing".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), burn_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Paused {
+            operation: Operation::Burn
+        }
+    );
+
+    // hand over admin rights, the old admin cannot unpause anymore
+    let update_msg = ExecuteMsg::UpdateAdmin {
+        admin: Some(String::from("lancelot")),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), update_msg)
+        .unwrap();
+    let unpause_msg = ExecuteMsg::Unpause { operations: None };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), admin, unpause_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let new_admin = mock_info("lancelot", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), new_admin, unpause_msg)
+        .unwrap();
+
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, transfer_msg)
+        .unwrap();
+    let res = contract.pause_status(deps.as_ref()).unwrap();
+    assert_eq!(
+        res,
+        PauseStatusResponse {
+            mint: false,
+            transfer: false,
+            approve: false,
+            burn: false,
+        }
+    );
+}
+
+#[test]
+fn locking_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER, &[]);
+    let owner = mock_info("demeter", &[]);
+    let env = mock_env();
+    let vesting_end = Expiration::AtHeight(env.block.height + 100);
+
+    // mint a vesting token, it is locked right away
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: Some("vested".to_string()),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: Some(vesting_end),
+    });
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg)
+        .unwrap();
+    mint(
+        &contract,
+        deps.as_mut(),
+        "free".to_string(),
+        "demeter".to_string(),
+    );
+
+    // only lockers can lock, and only the admin can add them
+    let lock_msg = ExecuteMsg::LockToken {
+        token_id: "free".to_string(),
+        lock_until: vesting_end,
+    };
+    let locker = mock_info("vesting", &[]);
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), locker.clone(), lock_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let add_msg = ExecuteMsg::AddLocker {
+        locker: String::from("vesting"),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), owner.clone(), add_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(deps.as_mut(), env.clone(), admin, add_msg)
+        .unwrap();
+    let res = contract.lockers(deps.as_ref(), None, None, None).unwrap();
+    assert_eq!(res.lockers, vec!["vesting".to_string()]);
+    contract
+        .execute(deps.as_mut(), env.clone(), locker.clone(), lock_msg)
+        .unwrap();
+
+    // an active lock can be extended, but not shortened
+    let shorten_msg = ExecuteMsg::LockToken {
+        token_id: "vested".to_string(),
+        lock_until: Expiration::AtHeight(env.block.height + 1),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), locker.clone(), shorten_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::LockShortened {
+            token_id: "vested".to_string(),
+            lock_until: vesting_end,
+        }
+    );
+    let extended = Expiration::AtHeight(env.block.height + 200);
+    let extend_msg = ExecuteMsg::LockToken {
+        token_id: "free".to_string(),
+        lock_until: extended,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), locker, extend_msg)
         .unwrap();
+
+    let res = contract
+        .locks(
+            deps.as_ref(),
+            env.clone(),
+            "demeter".to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.locks,
+        vec![
+            TokenLock {
+                token_id: "free".to_string(),
+                lock_until: extended,
+            },
+            TokenLock {
+                token_id: "vested".to_string(),
+                lock_until: vesting_end,
+            },
+        ]
+    );
+
+    // neither transfer nor burn before the lock expires
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("ceres"),
+        token_id: "vested".to_string(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            owner.clone(),
+            transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Locked {
+            token_id: "vested".to_string(),
+            lock_until: vesting_end,
+        }
+    );
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "free".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), owner.clone(), burn_msg)
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Locked { .. }));
+
+    // once expired, the token moves freely and is not listed anymore
+    let mut later = env;
+    later.block.height += 100;
+    contract
+        .execute(deps.as_mut(), later.clone(), owner, transfer_msg)
+        .unwrap();
+    for (owner, locks) in [
+        (
+            "demeter",
+            vec![TokenLock {
+                token_id: "free".to_string(),
+                lock_until: extended,
+            }],
+        ),
+        ("ceres", vec![]),
+    ] {
+        let res = contract
+            .locks(
+                deps.as_ref(),
+                later.clone(),
+                owner.to_string(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(res.locks, locks);
+    }
+
+    // expired locks still count towards the limit, so pages continue after the last one read
+    later.block.height += 100;
+    let res = contract
+        .locks(
+            deps.as_ref(),
+            later.clone(),
+            "demeter".to_string(),
+            None,
+            Some(1),
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.locks, vec![]);
+    assert_eq!(res.last_scanned, Some("free".to_string()));
+    let res = contract
+        .locks(
+            deps.as_ref(),
+            later,
+            "demeter".to_string(),
+            res.last_scanned,
+            Some(1),
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.locks, vec![]);
+    assert_eq!(res.last_scanned, None);
+}
+
+const COMPLIANCE: &str = "compliance";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ComplianceQueryMsg {
+    CheckTransfer(Cw721TransferCheck),
+}
+
+/// Answers the transfer checks of the compliance hook, which blocks everything touching "mallory"
+struct ComplianceQuerier(MockQuerier);
+
+impl Querier for ComplianceQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = from_slice(bin_request).unwrap();
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == COMPLIANCE =>
+            {
+                let ComplianceQueryMsg::CheckTransfer(check) = from_binary(&msg).unwrap();
+                let blocked = Some("mallory".to_string());
+                let res = if check.from == blocked || check.to == blocked {
+                    CheckTransferResponse {
+                        allowed: false,
+                        reason: Some("sanctioned".to_string()),
+                    }
+                } else {
+                    CheckTransferResponse {
+                        allowed: true,
+                        reason: None,
+                    }
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+            }
+            _ => self.0.raw_query(bin_request),
+        }
+    }
+}
+
+#[test]
+fn transfer_hooks() {
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: ComplianceQuerier(MockQuerier::default()),
+        custom_query_type: PhantomData,
+    };
+    let contract = setup_contract(deps.as_mut());
+    let rejected = ContractError::TransferRejected {
+        hook: COMPLIANCE.to_string(),
+        reason: "sanctioned".to_string(),
+    };
+
+    // only the admin can register hooks
+    let add_msg = ExecuteMsg::AddTransferHook {
+        hook: COMPLIANCE.to_string(),
+    };
+    let minter = mock_info(MINTER, &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), add_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), add_msg)
+        .unwrap();
+    let res = contract.transfer_hooks(deps.as_ref()).unwrap();
+    assert_eq!(res.hooks, vec![COMPLIANCE.to_string()]);
+
+    // cannot mint to a blocked address
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: Some("grow".to_string()),
+        owner: String::from("mallory"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .unwrap_err();
+    assert_eq!(err, rejected);
+
+    // nor transfer or send to it
+    mint(
+        &contract,
+        deps.as_mut(),
+        "grow".to_string(),
+        "demeter".to_string(),
+    );
+    let owner = mock_info("demeter", &[]);
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("mallory"),
+        token_id: "grow".to_string(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, rejected);
+    let send_msg = ExecuteMsg::SendNft {
+        contract: String::from("mallory"),
+        token_id: "grow".to_string(),
+        msg: to_binary("hello").unwrap(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), send_msg)
+        .unwrap_err();
+    assert_eq!(err, rejected);
+
+    // other transfers go through
+    let transfer_ok = ExecuteMsg::TransferNft {
+        recipient: String::from("ceres"),
+        token_id: "grow".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), transfer_ok)
+        .unwrap();
+
+    // once removed, the hook is not queried anymore
+    let remove_msg = ExecuteMsg::RemoveTransferHook {
+        hook: COMPLIANCE.to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), admin, remove_msg)
+        .unwrap();
+    let ceres = mock_info("ceres", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), ceres, transfer_msg)
+        .unwrap();
+}
+
+#[test]
+fn subscriber_hooks() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // only the admin can register subscribers
+    let add_staking = ExecuteMsg::AddSubscriber {
+        subscriber: String::from("staking"),
+        fail_open: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            add_staking.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), add_staking)
+        .unwrap();
+    let add_rewards = ExecuteMsg::AddSubscriber {
+        subscriber: String::from("rewards"),
+        fail_open: Some(true),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), add_rewards)
+        .unwrap();
+    let res = contract.subscribers(deps.as_ref()).unwrap();
+    assert_eq!(
+        res.subscribers,
+        vec![
+            Subscriber {
+                address: String::from("rewards"),
+                fail_open: true,
+            },
+            Subscriber {
+                address: String::from("staking"),
+                fail_open: false,
+            },
+        ]
+    );
+
+    // both are notified, only the fail-open one wants a reply
+    let notifications = |msg: Cw721HookMsg| {
+        vec![
+            SubMsg::reply_on_error(msg.clone().into_cosmos_msg("rewards").unwrap(), 1),
+            SubMsg::new(msg.into_cosmos_msg("staking").unwrap()),
+        ]
+    };
+    let res = mint(
+        &contract,
+        deps.as_mut(),
+        "coin".to_string(),
+        "alice".to_string(),
+    );
+    assert_eq!(
+        res.messages,
+        notifications(Cw721HookMsg::Minted {
+            owner: String::from("alice"),
+            token_id: String::from("coin"),
+        })
+    );
+
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("coin"),
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        notifications(Cw721HookMsg::Transferred {
+            from: String::from("alice"),
+            to: String::from("bob"),
+            token_id: String::from("coin"),
+        })
+    );
+
+    // a failing fail-open subscriber is ignored
+    let reply = Reply {
+        id: 1,
+        result: ContractResult::Err("out of rewards".to_string()),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "subscriber_failed")
+            .add_attribute("error", "out of rewards")
+    );
+    // nor does a successful reply break anything
+    let reply = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(res, Response::new());
+
+    // removed subscribers are not notified anymore
+    let remove_msg = ExecuteMsg::RemoveSubscriber {
+        subscriber: String::from("rewards"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), admin, remove_msg)
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("coin"),
+    };
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), burn_msg)
+        .unwrap();
+    let burned = Cw721HookMsg::Burned {
+        owner: String::from("bob"),
+        token_id: String::from("coin"),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(burned.into_cosmos_msg("staking").unwrap())]
+    );
+}
+
+#[cfg(feature = "provenance")]
+#[test]
+fn token_history() {
+    use crate::{ProvenanceAction, ProvenanceRecord};
+
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let record = |index, owner: &str, env: &cosmwasm_std::Env, action| ProvenanceRecord {
+        index,
+        owner: owner.to_string(),
+        height: env.block.height,
+        time: env.block.time,
+        action,
+    };
+
+    mint(
+        &contract,
+        deps.as_mut(),
+        "art".to_string(),
+        "alice".to_string(),
+    );
+    let minted = record(0, "alice", &env, ProvenanceAction::Mint);
+
+    env.block.height += 1;
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("art"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let transferred = record(1, "bob", &env, ProvenanceAction::Transfer);
+
+    env.block.height += 1;
+    let send_msg = ExecuteMsg::SendNft {
+        contract: String::from("gallery"),
+        token_id: String::from("art"),
+        msg: to_binary("display").unwrap(),
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), send_msg)
+        .unwrap();
+    let sent = record(2, "gallery", &env, ProvenanceAction::Send);
+
+    let res = contract
+        .token_history(deps.as_ref(), "art".to_string(), None, None, None)
+        .unwrap();
+    assert_eq!(res.history, vec![minted, transferred.clone(), sent.clone()]);
+    let res = contract
+        .token_history(deps.as_ref(), "art".to_string(), Some(0), Some(1), None)
+        .unwrap();
+    assert_eq!(res.history, vec![transferred]);
+
+    // only the admin can cap the history
+    let retention_msg = ExecuteMsg::SetHistoryRetention { max_entries: 2 };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("gallery", &[]),
+            retention_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            retention_msg,
+        )
+        .unwrap();
+
+    // the history outlives the token, the oldest entries are dropped
+    env.block.height += 1;
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("art"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("gallery", &[]),
+            burn_msg,
+        )
+        .unwrap();
+    let burned = record(3, "gallery", &env, ProvenanceAction::Burn);
+    let res = contract
+        .token_history(deps.as_ref(), "art".to_string(), None, None, None)
+        .unwrap();
+    assert_eq!(res.history, vec![sent, burned]);
+}
+
+#[test]
+fn snapshot_queries() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+
+    // snapshot queries are refused unless enabled
+    let mut msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        admin: None,
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: None,
+        governance: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+    contract
+        .balance_at_height(deps.as_ref(), "alice".to_string(), 1)
+        .unwrap_err();
+
+    let mut deps = mock_dependencies();
+    msg.snapshots = Some(true);
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+
+    let mut env = mock_env();
+    let minted_at = env.block.height;
+    for token_id in ["bird", "fish"] {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: Some(token_id.to_string()),
+            owner: String::from("alice"),
+            token_uri: None,
+            extension: None,
+            non_transferable: None,
+            lock_until: None,
+        });
+        contract
+            .execute(deps.as_mut(), env.clone(), mock_info(MINTER, &[]), mint_msg)
+            .unwrap();
+    }
+
+    env.block.height += 10;
+    let transferred_at = env.block.height;
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("bird"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+
+    env.block.height += 10;
+    let burned_at = env.block.height;
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("fish"),
+    };
+    contract
+        .execute(deps.as_mut(), env, mock_info("alice", &[]), burn_msg)
+        .unwrap();
+
+    // heights are looked at from their start, before any change in that block
+    let owner_of = |token_id: &str, height| {
+        contract
+            .owner_of_at_height(deps.as_ref(), token_id.to_string(), height)
+            .unwrap()
+            .owner
+    };
+    assert_eq!(owner_of("bird", minted_at), None);
+    assert_eq!(owner_of("bird", minted_at + 1), Some("alice".to_string()));
+    assert_eq!(owner_of("bird", transferred_at), Some("alice".to_string()));
+    assert_eq!(
+        owner_of("bird", transferred_at + 1),
+        Some("bob".to_string())
+    );
+    assert_eq!(owner_of("fish", burned_at), Some("alice".to_string()));
+    assert_eq!(owner_of("fish", burned_at + 1), None);
+
+    let tokens_of = |owner: &str, height| {
+        contract
+            .tokens_at_height(deps.as_ref(), owner.to_string(), height, None, None, None)
+            .unwrap()
+            .tokens
+    };
+    let balance_of = |owner: &str, height| {
+        contract
+            .balance_at_height(deps.as_ref(), owner.to_string(), height)
+            .unwrap()
+            .balance
+    };
+    assert_eq!(tokens_of("alice", minted_at), Vec::<String>::new());
+    assert_eq!(balance_of("alice", minted_at), 0);
+    assert_eq!(tokens_of("alice", transferred_at), vec!["bird", "fish"]);
+    assert_eq!(balance_of("alice", transferred_at), 2);
+    assert_eq!(tokens_of("alice", burned_at), vec!["fish"]);
+    assert_eq!(tokens_of("bob", burned_at), vec!["bird"]);
+    assert_eq!(balance_of("bob", burned_at), 1);
+    assert_eq!(tokens_of("alice", burned_at + 1), Vec::<String>::new());
+    assert_eq!(balance_of("alice", burned_at + 1), 0);
+
+    // pages are bounded by the tokens read, not found, so one can come back empty
+    let page_of = |start_after: Option<&str>| {
+        contract
+            .tokens_at_height(
+                deps.as_ref(),
+                "alice".to_string(),
+                burned_at,
+                start_after.map(String::from),
+                Some(1),
+                None,
+            )
+            .unwrap()
+    };
+    assert_eq!(
+        page_of(None),
+        TokensAtHeightResponse {
+            tokens: vec![],
+            last_scanned: Some("bird".to_string()),
+        }
+    );
+    assert_eq!(
+        page_of(Some("bird")),
+        TokensAtHeightResponse {
+            tokens: vec!["fish".to_string()],
+            last_scanned: Some("fish".to_string()),
+        }
+    );
+    assert_eq!(
+        page_of(Some("fish")),
+        TokensAtHeightResponse {
+            tokens: vec![],
+            last_scanned: None,
+        }
+    );
+}
+
+#[test]
+fn balances() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let balance =
+        |deps: Deps, owner: &str| contract.balance(deps, owner.to_string()).unwrap().balance;
+
+    mint(
+        &contract,
+        deps.as_mut(),
+        "1".to_string(),
+        "alice".to_string(),
+    );
+    mint(
+        &contract,
+        deps.as_mut(),
+        "2".to_string(),
+        "alice".to_string(),
+    );
+    mint(&contract, deps.as_mut(), "3".to_string(), "bob".to_string());
+    assert_eq!(balance(deps.as_ref(), "alice"), 2);
+    assert_eq!(balance(deps.as_ref(), "bob"), 1);
+
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("1"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("2"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn_msg)
+        .unwrap();
+    assert_eq!(balance(deps.as_ref(), "alice"), 0);
+    assert_eq!(balance(deps.as_ref(), "bob"), 2);
+    // owners without tokens are not kept around
+    assert!(!contract
+        .balances
+        .has(&deps.storage, &Addr::unchecked("alice")));
+
+    // collections deployed before balances were tracked get them on migration
+    mint(
+        &contract,
+        deps.as_mut(),
+        "4".to_string(),
+        "carol".to_string(),
+    );
+    for owner in ["bob", "carol"] {
+        contract
+            .balances
+            .remove(&mut deps.storage, &Addr::unchecked(owner));
+    }
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), 2);
+    assert_eq!(balance(deps.as_ref(), "carol"), 1);
+    assert_eq!(contract.num_owners(deps.as_ref()).unwrap().count, 2);
+}
+
+#[test]
+fn migrating_checks_the_contract() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // other contracts cannot be migrated into this one
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.12.0").unwrap();
+    let err = contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WrongContract {
+            contract: "crates.io:cw20-base".to_string(),
+        }
+    );
+
+    // nor can newer versions be downgraded
+    set_contract_version(&mut deps.storage, "crates.io:cw721-base", "99.0.0").unwrap();
+    let err = contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CannotDowngrade {
+            from: "99.0.0".to_string(),
+            to: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+
+    // older versions are upgraded
+    set_contract_version(&mut deps.storage, "crates.io:cw721-base", "0.11.1").unwrap();
+    for token_id in ["3", "12", "moon"] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            "alice".to_string(),
+        );
+    }
+    contract.next_token_id.remove(&mut deps.storage);
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    // and continue the sequential ids after the highest one minted before
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: None,
+        owner: String::from("alice"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+    let data: MintResponse = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(data.token_id, "13");
+}
+
+#[test]
+fn listing_owners() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    for (token_id, owner) in [("1", "carol"), ("2", "alice"), ("3", "bob"), ("4", "alice")] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            owner.to_string(),
+        );
+    }
+    assert_eq!(contract.num_owners(deps.as_ref()).unwrap().count, 3);
+
+    let owner = |owner: &str, balance| OwnerBalance {
+        owner: owner.to_string(),
+        balance,
+    };
+    let res = contract
+        .all_owners(deps.as_ref(), None, Some(2), None)
+        .unwrap();
+    assert_eq!(res.owners, vec![owner("alice", 2), owner("bob", 1)]);
+    let res = contract
+        .all_owners(deps.as_ref(), Some("bob".to_string()), None, None)
+        .unwrap();
+    assert_eq!(res.owners, vec![owner("carol", 1)]);
+
+    // carol is gone once their only token is, alice stays with one left
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("1"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("carol", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("2"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn_msg)
+        .unwrap();
+    let res = contract
+        .all_owners(deps.as_ref(), None, None, None)
+        .unwrap();
+    assert_eq!(res.owners, vec![owner("alice", 1), owner("bob", 2)]);
+    assert_eq!(contract.num_owners(deps.as_ref()).unwrap().count, 2);
+}
+
+#[test]
+fn approvals_for_operator() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let expires = Expiration::AtHeight(mock_env().block.height + 10);
+    for (owner, expires) in [("carol", None), ("alice", Some(expires)), ("bob", None)] {
+        let approve_all_msg = ExecuteMsg::ApproveAll {
+            operator: String::from("market"),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(owner, &[]),
+                approve_all_msg,
+            )
+            .unwrap();
+    }
+    // other operators are not listed
+    let approve_all_msg = ExecuteMsg::ApproveAll {
+        operator: String::from("buddy"),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            approve_all_msg,
+        )
+        .unwrap();
+
+    let grant = |granter: &str, expires| OperatorGrant {
+        granter: granter.to_string(),
+        expires,
+    };
+    let res = contract
+        .approvals_for_operator(
+            deps.as_ref(),
+            mock_env(),
+            String::from("market"),
+            false,
+            None,
+            Some(2),
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.grants,
+        vec![grant("alice", expires), grant("bob", Expiration::Never {})]
+    );
+    let res = contract
+        .approvals_for_operator(
+            deps.as_ref(),
+            mock_env(),
+            String::from("market"),
+            false,
+            Some(String::from("bob")),
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.grants, vec![grant("carol", Expiration::Never {})]);
+
+    // revoked grants are removed, expired ones only shown on request
+    let revoke_all_msg = ExecuteMsg::RevokeAll {
+        operator: String::from("market"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("carol", &[]),
+            revoke_all_msg,
+        )
+        .unwrap();
+    let mut late_env = mock_env();
+    late_env.block.height += 10;
+    let res = contract
+        .approvals_for_operator(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("market"),
+            false,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.grants, vec![grant("bob", Expiration::Never {})]);
+    let all_grants = |deps: Deps| {
+        contract
+            .approvals_for_operator(
+                deps,
+                late_env.clone(),
+                String::from("market"),
+                true,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .grants
+    };
+    assert_eq!(
+        all_grants(deps.as_ref()),
+        vec![grant("alice", expires), grant("bob", Expiration::Never {})]
+    );
+
+    // collections deployed before the reverse index get it on migration
+    for owner in ["alice", "bob"] {
+        contract.operator_grants.remove(
+            &mut deps.storage,
+            (&Addr::unchecked("market"), &Addr::unchecked(owner)),
+        );
+    }
+    assert_eq!(all_grants(deps.as_ref()), vec![]);
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_eq!(
+        all_grants(deps.as_ref()),
+        vec![grant("alice", expires), grant("bob", Expiration::Never {})]
+    );
+}
+
+#[test]
+fn tokens_approved_for() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    for token_id in ["1", "2", "3", "4"] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            "alice".to_string(),
+        );
+    }
+    let expires = Expiration::AtHeight(mock_env().block.height + 10);
+    for (token_id, expires) in [("1", None), ("2", Some(expires)), ("3", None), ("4", None)] {
+        let approve_msg = ExecuteMsg::Approve {
+            spender: String::from("market"),
+            token_id: token_id.to_string(),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &[]),
+                approve_msg,
+            )
+            .unwrap();
+    }
+    let approved = |deps: Deps, env: Env, include_expired, start_after: Option<&str>| {
+        contract
+            .tokens_approved_for(
+                deps,
+                env,
+                String::from("market"),
+                include_expired,
+                start_after.map(String::from),
+                None,
+                None,
+            )
+            .unwrap()
+            .tokens
+    };
+    assert_eq!(
+        approved(deps.as_ref(), mock_env(), false, Some("1")),
+        vec!["2", "3", "4"]
+    );
+
+    // revoked, transferred and burned tokens are dropped
+    let revoke_msg = ExecuteMsg::Revoke {
+        spender: String::from("market"),
+        token_id: String::from("1"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            revoke_msg,
+        )
+        .unwrap();
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("bob"),
+        token_id: String::from("3"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("market", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("4"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn_msg)
+        .unwrap();
+    assert_eq!(approved(deps.as_ref(), mock_env(), false, None), vec!["2"]);
+
+    // expired approvals are only shown on request
+    let mut late_env = mock_env();
+    late_env.block.height += 10;
+    assert!(approved(deps.as_ref(), late_env.clone(), false, None).is_empty());
+    assert_eq!(approved(deps.as_ref(), late_env, true, None), vec!["2"]);
+
+    // collections deployed before the index get it on migration
+    contract
+        .spender_approvals
+        .remove(&mut deps.storage, (&Addr::unchecked("market"), "2"));
+    assert!(approved(deps.as_ref(), mock_env(), false, None).is_empty());
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_eq!(approved(deps.as_ref(), mock_env(), false, None), vec!["2"]);
+}
+
+#[test]
+fn pruning_expired_grants() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let soon = Expiration::AtHeight(mock_env().block.height + 10);
+    for (operator, expires) in [
+        ("aaron", Some(soon)),
+        ("bella", None),
+        ("chris", Some(soon)),
+    ] {
+        let approve_all_msg = ExecuteMsg::ApproveAll {
+            operator: operator.to_string(),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("alice", &[]),
+                approve_all_msg,
+            )
+            .unwrap();
+    }
+    for token_id in ["1", "2"] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            "alice".to_string(),
+        );
+        for (spender, expires) in [("aaron", Some(soon)), ("bella", None)] {
+            let approve_msg = ExecuteMsg::Approve {
+                spender: spender.to_string(),
+                token_id: token_id.to_string(),
+                expires,
+            };
+            contract
+                .execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info("alice", &[]),
+                    approve_msg,
+                )
+                .unwrap();
+        }
+    }
+
+    // nothing is expired yet
+    let prune_msg = ExecuteMsg::PruneExpired {
+        owner: String::from("alice"),
+        start_after: None,
+        limit: None,
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            prune_msg,
+        )
+        .unwrap();
+    assert_eq!(res.attributes[3], attr("pruned", "0"));
+
+    // anyone can prune in batches
+    let mut late_env = mock_env();
+    late_env.block.height += 10;
+    let operators = |deps: Deps| {
+        contract
+            .operators(
+                deps,
+                late_env.clone(),
+                String::from("alice"),
+                true,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .operators
+            .into_iter()
+            .map(|op| op.spender)
+            .collect::<Vec<_>>()
+    };
+    // the limit bounds the operators read, so a batch can prune nothing
+    let mut start_after = None;
+    for (pruned, left) in [
+        (1, vec!["bella", "chris"]),
+        (0, vec!["bella", "chris"]),
+        (1, vec!["bella"]),
+    ] {
+        let prune_msg = ExecuteMsg::PruneExpired {
+            owner: String::from("alice"),
+            start_after,
+            limit: Some(1),
+        };
+        let res = contract
+            .execute(
+                deps.as_mut(),
+                late_env.clone(),
+                mock_info("anyone", &[]),
+                prune_msg,
+            )
+            .unwrap();
+        assert_eq!(res.attributes[3], attr("pruned", pruned.to_string()));
+        assert_eq!(operators(deps.as_ref()), left);
+        assert_eq!(res.attributes[4].key, "next_start_after");
+        start_after = Some(res.attributes[4].value.clone());
+    }
+    // until there is nothing left to read
+    let prune_msg = ExecuteMsg::PruneExpired {
+        owner: String::from("alice"),
+        start_after,
+        limit: Some(1),
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("anyone", &[]),
+            prune_msg,
+        )
+        .unwrap();
+    assert_eq!(res.attributes.len(), 4);
+    let grants = contract
+        .approvals_for_operator(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("chris"),
+            true,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(grants.grants.is_empty());
+
+    // tokens are pruned 30 at most at once
+    let prune_msg = ExecuteMsg::PruneTokenApprovals {
+        token_ids: vec![String::from("1"); 31],
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("anyone", &[]),
+            prune_msg,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyTokens { max: 30 });
+
+    // unknown tokens are skipped
+    let prune_msg = ExecuteMsg::PruneTokenApprovals {
+        token_ids: vec![String::from("1"), String::from("gone")],
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("anyone", &[]),
+            prune_msg,
+        )
+        .unwrap();
+    assert_eq!(res.attributes[2], attr("pruned", "1"));
+    let approvals = contract
+        .approvals(deps.as_ref(), late_env.clone(), String::from("1"), true)
+        .unwrap();
+    assert_eq!(
+        approvals.approvals,
+        vec![cw721::Approval {
+            spender: String::from("bella"),
+            expires: Expiration::Never {},
+        }]
+    );
+    let approved = contract
+        .tokens_approved_for(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("aaron"),
+            true,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(approved.tokens, vec!["2"]);
+
+    // approving drops the expired approvals of the token as well
+    let revoke_msg = ExecuteMsg::Revoke {
+        spender: String::from("bella"),
+        token_id: String::from("2"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("alice", &[]),
+            revoke_msg,
+        )
+        .unwrap();
+    let approvals = contract
+        .approvals(deps.as_ref(), late_env.clone(), String::from("2"), true)
+        .unwrap();
+    assert!(approvals.approvals.is_empty());
+    let approved = contract
+        .tokens_approved_for(
+            deps.as_ref(),
+            late_env,
+            String::from("aaron"),
+            true,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(approved.tokens.is_empty());
+}
+
+#[test]
+fn approval_cap() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        admin: Some(String::from(ADMIN)),
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: Some(2),
+        token_id_rules: None,
+        max_supply: None,
+        governance: None,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+    mint(
+        &contract,
+        deps.as_mut(),
+        "1".to_string(),
+        "alice".to_string(),
+    );
+
+    let approve = |deps: DepsMut, env, spender: &str, expires| {
+        let approve_msg = ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: String::from("1"),
+            expires,
+        };
+        contract.execute(deps, env, mock_info("alice", &[]), approve_msg)
+    };
+    let soon = Expiration::AtHeight(mock_env().block.height + 10);
+    approve(deps.as_mut(), mock_env(), "aaron", Some(soon)).unwrap();
+    approve(deps.as_mut(), mock_env(), "bella", None).unwrap();
+    let err = approve(deps.as_mut(), mock_env(), "chris", None).unwrap_err();
+    assert_eq!(err, ContractError::TooManyApprovals { max: 2 });
+    // renewing an approval does not add one
+    approve(deps.as_mut(), mock_env(), "aaron", Some(soon)).unwrap();
+    // and expired approvals make room
+    let mut late_env = mock_env();
+    late_env.block.height += 10;
+    approve(deps.as_mut(), late_env, "chris", None).unwrap();
+
+    // the admin can lower the cap, tokens keep the approvals they have
+    let cap_msg = ExecuteMsg::SetMaxApprovals { max_approvals: 1 };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            cap_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), cap_msg)
+        .unwrap();
+    let approvals = contract
+        .approvals(deps.as_ref(), mock_env(), String::from("1"), false)
+        .unwrap();
+    assert_eq!(approvals.approvals.len(), 2);
+    let err = approve(deps.as_mut(), mock_env(), "dora", None).unwrap_err();
+    assert_eq!(err, ContractError::TooManyApprovals { max: 1 });
+
+    // only the owner or an operator can revoke all approvals
+    let revoke_msg = ExecuteMsg::RevokeAllApprovals {
+        token_id: String::from("1"),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bella", &[]),
+            revoke_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            revoke_msg,
+        )
+        .unwrap();
+    let token_uri = Some(String::from("https://lunapunks.io/1"));
+    assert_eq!(
+        res.events,
+        vec![
+            Cw721Event::revoke("alice", "alice", "bella", "1", token_uri.clone()).into(),
+            Cw721Event::revoke("alice", "alice", "chris", "1", token_uri).into(),
+        ]
+    );
+    let approvals = contract
+        .approvals(deps.as_ref(), mock_env(), String::from("1"), true)
+        .unwrap();
+    assert!(approvals.approvals.is_empty());
+    let approved = contract
+        .tokens_approved_for(
+            deps.as_ref(),
+            mock_env(),
+            String::from("bella"),
+            true,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(approved.tokens.is_empty());
+}
+
+#[test]
+fn sequential_token_ids() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mint_next = |deps: DepsMut| {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: None,
+            owner: String::from("alice"),
+            token_uri: None,
+            extension: None,
+            non_transferable: None,
+            lock_until: None,
+        });
+        let res = contract
+            .execute(deps, mock_env(), mock_info(MINTER, &[]), mint_msg)
+            .unwrap();
+        let data: MintResponse = from_binary(&res.data.unwrap()).unwrap();
+        data.token_id
+    };
+
+    let burn = |deps: DepsMut, owner: &str, token_id: &str| {
+        let burn_msg = ExecuteMsg::Burn {
+            token_id: token_id.to_string(),
+        };
+        contract
+            .execute(deps, mock_env(), mock_info(owner, &[]), burn_msg)
+            .unwrap();
+    };
+
+    // explicitly minted ids move the sequence past them, so they are not reused after a burn
+    mint(&contract, deps.as_mut(), "5".to_string(), "bob".to_string());
+    burn(deps.as_mut(), "bob", "5");
+    assert_eq!(mint_next(deps.as_mut()), "6");
+    assert_eq!(mint_next(deps.as_mut()), "7");
+
+    // lower and non-numeric ids leave it alone
+    for token_id in ["3", "eight"] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            "bob".to_string(),
+        );
+    }
+    assert_eq!(mint_next(deps.as_mut()), "8");
+
+    // burned ids are not reused, even though the token count goes down
+    burn(deps.as_mut(), "alice", "8");
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap().count, 4);
+    assert_eq!(mint_next(deps.as_mut()), "9");
+
+    let owner = contract
+        .owner_of(deps.as_ref(), mock_env(), String::from("9"), false)
+        .unwrap();
+    assert_eq!(owner.owner, "alice");
+
+    // only ids in the same format count as numeric
+    for token_id in ["+12", "013"] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            "bob".to_string(),
+        );
+    }
+    assert_eq!(mint_next(deps.as_mut()), "10");
+
+    // the last id cannot be reserved, or there would be no next one
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: Some(u64::MAX.to_string()),
+        owner: String::from("bob"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::TokenIdsExhausted {});
+    mint(
+        &contract,
+        deps.as_mut(),
+        (u64::MAX - 1).to_string(),
+        "bob".to_string(),
+    );
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: None,
+        owner: String::from("alice"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::TokenIdsExhausted {});
+}
+
+#[test]
+fn mint_order() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    for (token_id, owner) in [
+        ("2", "alice"),
+        ("10", "bob"),
+        ("1", "alice"),
+        ("100", "alice"),
+    ] {
+        mint(
+            &contract,
+            deps.as_mut(),
+            token_id.to_string(),
+            owner.to_string(),
+        );
+    }
+    let listed = |deps: Deps, owner: Option<&str>, start_after: Option<&str>, order| {
+        contract
+            .tokens_in_mint_order(
+                deps,
+                owner.map(String::from),
+                start_after.map(String::from),
+                Some(2),
+                None,
+                order,
+            )
+            .unwrap()
+            .tokens
+    };
+
+    // by default, tokens are listed by token_id
+    let query_msg = QueryMsg::AllTokens {
+        start_after: None,
+        limit: None,
+        page: None,
+        order: None,
+    };
+    let res: TokensResponse = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), query_msg)
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.tokens, vec!["1", "10", "100", "2"]);
+    let query_msg = QueryMsg::AllTokens {
+        start_after: Some(String::from("10")),
+        limit: None,
+        page: None,
+        order: Some(MintOrder::Ascending),
+    };
+    let res: TokensResponse = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), query_msg)
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.tokens, vec!["1", "100"]);
+
+    let asc = MintOrder::Ascending;
+    let desc = MintOrder::Descending;
+    assert_eq!(listed(deps.as_ref(), None, None, asc), vec!["2", "10"]);
+    assert_eq!(listed(deps.as_ref(), None, None, desc), vec!["100", "1"]);
+    assert_eq!(
+        listed(deps.as_ref(), None, Some("1"), desc),
+        vec!["10", "2"]
+    );
+    assert_eq!(
+        listed(deps.as_ref(), Some("alice"), None, asc),
+        vec!["2", "1"]
+    );
+    assert_eq!(
+        listed(deps.as_ref(), Some("alice"), Some("1"), asc),
+        vec!["100"]
+    );
+
+    // transfers keep the mint order
+    let transfer_msg = ExecuteMsg::TransferNft {
+        recipient: String::from("alice"),
+        token_id: String::from("10"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        listed(deps.as_ref(), Some("alice"), Some("2"), asc),
+        vec!["10", "1"]
+    );
+    assert!(listed(deps.as_ref(), Some("bob"), None, asc).is_empty());
+
+    // collections deployed before get their tokens ordered by ID on migration
+    for token_id in ["1", "2", "10", "100"] {
+        let mut token = contract.tokens.load(&deps.storage, token_id).unwrap();
+        token.mint_seq = 0;
+        contract
+            .tokens
+            .save(&mut deps.storage, token_id, &token)
+            .unwrap();
+    }
+    contract.mint_count.remove(&mut deps.storage);
+    contract
+        .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+        .unwrap();
+    assert_eq!(listed(deps.as_ref(), None, None, asc), vec!["1", "2"]);
+    assert_eq!(listed(deps.as_ref(), None, None, desc), vec!["100", "10"]);
+    assert_eq!(contract.mint_count(&deps.storage).unwrap(), 4);
+
+    // the last token of a page can be burned before the next one is loaded
+    assert_eq!(listed(deps.as_ref(), None, None, asc), vec!["1", "2"]);
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("2"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn_msg)
+        .unwrap();
+    assert_eq!(
+        listed(deps.as_ref(), None, Some("2"), asc),
+        vec!["10", "100"]
+    );
+    assert_eq!(listed(deps.as_ref(), None, Some("2"), desc), vec!["1"]);
+    // but unknown tokens are still rejected
+    contract
+        .tokens_in_mint_order(
+            deps.as_ref(),
+            None,
+            Some(String::from("3")),
+            None,
+            None,
+            asc,
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn token_id_rules() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+    let instantiate = |deps: DepsMut, rules| {
+        let msg = InstantiateMsg {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            minter: String::from(MINTER),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: Some(rules),
+            max_supply: None,
+            governance: None,
+        };
+        contract.instantiate(deps, mock_env(), mock_info("creator", &[]), msg)
+    };
+    let mint_id = |deps: DepsMut, token_id: &str| {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: Some(token_id.to_string()),
+            owner: String::from("alice"),
+            token_uri: None,
+            extension: None,
+            non_transferable: None,
+            lock_until: None,
+        });
+        contract.execute(deps, mock_env(), mock_info(MINTER, &[]), mint_msg)
+    };
+    let invalid = |reason: &str| ContractError::InvalidTokenId {
+        reason: reason.to_string(),
+    };
+
+    // without rules, any token id is accepted
+    setup_contract(deps.as_mut());
+    mint_id(deps.as_mut(), "a b").unwrap();
+    let res = contract.token_id_rules(deps.as_ref()).unwrap();
+    assert_eq!(res.max_length, None);
+
+    // malformed charsets are rejected up front
+    let rules = TokenIdRules {
+        max_length: None,
+        charset: Some(String::from("z-a")),
+        numeric_only: None,
+    };
+    instantiate(deps.as_mut(), rules).unwrap_err();
+
+    let rules = TokenIdRules {
+        max_length: Some(8),
+        charset: Some(String::from("a-z0-9_-")),
+        numeric_only: None,
+    };
+    instantiate(deps.as_mut(), rules).unwrap();
+    let res = contract.token_id_rules(deps.as_ref()).unwrap();
+    assert_eq!(
+        res,
+        TokenIdRulesResponse {
+            max_length: Some(8),
+            charset: Some(String::from("a-z0-9_-")),
+            numeric_only: false,
+        }
+    );
+
+    mint_id(deps.as_mut(), "punk_0-x").unwrap();
+    let err = mint_id(deps.as_mut(), "").unwrap_err();
+    assert_eq!(err, invalid("empty"));
+    let err = mint_id(deps.as_mut(), "a b").unwrap_err();
+    assert_eq!(err, invalid("contains whitespace or control characters"));
+    let err = mint_id(deps.as_mut(), "x\u{7}").unwrap_err();
+    assert_eq!(err, invalid("contains whitespace or control characters"));
+    let err = mint_id(deps.as_mut(), "Punk").unwrap_err();
+    assert_eq!(err, invalid("character 'P' is not allowed"));
+    let err = mint_id(deps.as_mut(), "punk_01-x").unwrap_err();
+    assert_eq!(err, invalid("longer than 8 bytes"));
+
+    // numeric ids only
+    let rules = TokenIdRules {
+        max_length: None,
+        charset: None,
+        numeric_only: Some(true),
+    };
+    instantiate(deps.as_mut(), rules).unwrap();
+    mint_id(deps.as_mut(), "0").unwrap();
+    mint_id(deps.as_mut(), "42").unwrap();
+    for token_id in ["042", "4a", "-1"] {
+        let err = mint_id(deps.as_mut(), token_id).unwrap_err();
+        assert_eq!(err, invalid("not a canonical number"));
+    }
+
+    // sequential ids follow the rules too
+    let rules = TokenIdRules {
+        max_length: None,
+        charset: Some(String::from("a-z")),
+        numeric_only: None,
+    };
+    instantiate(deps.as_mut(), rules).unwrap();
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: None,
+        owner: String::from("alice"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap_err();
+    assert_eq!(err, invalid("character '4' is not allowed"));
+}
+
+#[test]
+fn max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        admin: None,
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: Some(2),
+        governance: None,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+
+    mint(
+        &contract,
+        deps.as_mut(),
+        "1".to_string(),
+        "alice".to_string(),
+    );
+    mint(
+        &contract,
+        deps.as_mut(),
+        "2".to_string(),
+        "alice".to_string(),
+    );
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: String::from("1"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn_msg)
+        .unwrap();
+    assert_eq!(
+        contract.supply_info(deps.as_ref()).unwrap(),
+        SupplyInfoResponse {
+            max_supply: Some(2),
+            minted: 2,
+            burned: 1,
+            circulating: 1,
+        }
+    );
+
+    // burned tokens still count against the max supply
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: None,
+        owner: String::from("alice"),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MaxSupplyReached { max_supply: 2 });
+}
+
+const VAULT: &str = "vault";
+
+/// Knows a single contract, the vault, every other address is an account
+struct ReceiverQuerier(MockQuerier);
+
+impl Querier for ReceiverQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = from_slice(bin_request).unwrap();
+        match request {
+            QueryRequest::Wasm(WasmQuery::ContractInfo { contract_addr })
+                if contract_addr == VAULT =>
+            {
+                let res = cosmwasm_std::ContractInfoResponse::new(1, "creator");
+                SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+            }
+            _ => self.0.raw_query(bin_request),
+        }
+    }
+}
+
+#[test]
+fn safe_send() {
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: ReceiverQuerier(MockQuerier::default()),
+        custom_query_type: PhantomData,
+    };
+    let contract = setup_contract(deps.as_mut());
+    mint(&contract, deps.as_mut(), "melt".into(), "venus".into());
+    mint(&contract, deps.as_mut(), "freeze".into(), "venus".into());
+    let payload = to_binary("deposit").unwrap();
+    let venus = mock_info("venus", &coins(5, "ustars"));
+
+    // accounts cannot receive a safe send
+    let send_msg = |contract: &str, token_id: &str| ExecuteMsg::SafeSendNft {
+        contract: contract.to_string(),
+        token_id: token_id.to_string(),
+        msg: payload.clone(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            venus.clone(),
+            send_msg("alice", "melt"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotAContract {
+            contract: "alice".to_string()
+        }
+    );
+
+    // the receiver gets the funds and is asked to reply
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            venus.clone(),
+            send_msg(VAULT, "melt"),
+        )
+        .unwrap();
+    let receive = Cw721ReceiveMsg {
+        sender: "venus".to_string(),
+        token_id: "melt".to_string(),
+        msg: payload.clone(),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            receive
+                .into_cosmos_msg_with_funds(VAULT, coins(5, "ustars"))
+                .unwrap(),
+            2
+        )]
+    );
+    let owner = contract
+        .owner_of(deps.as_ref(), mock_env(), "melt".into(), false)
+        .unwrap();
+    assert_eq!(owner.owner, VAULT);
+
+    let reply = Reply {
+        id: 2,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = contract
+        .reply(deps.as_mut(), mock_env(), reply.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "safe_send_received")
+            .add_attribute("recipient", VAULT)
+            .add_attribute("token_id", "melt")
+    );
+
+    // a rejection fails the whole send
+    contract
+        .execute(deps.as_mut(), mock_env(), venus, send_msg(VAULT, "freeze"))
+        .unwrap();
+    let rejected = Reply {
+        id: 2,
+        result: ContractResult::Err("wrong payload".to_string()),
+    };
+    let err = contract
+        .reply(deps.as_mut(), mock_env(), rejected)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ReceiverFailed {
+            contract: VAULT.to_string(),
+            token_id: "freeze".to_string(),
+            error: "wrong payload".to_string(),
+        }
+    );
+
+    // every pending send has been answered
+    contract
+        .reply(deps.as_mut(), mock_env(), reply)
+        .unwrap_err();
+}
+
+#[test]
+fn approve_with_permit() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    let key = SigningKey::from_bytes(&[1; 32]).unwrap();
+    let pubkey = Binary::from(key.verifying_key().to_bytes().to_vec());
+    let owner = signer_address(&pubkey, "cosmos").unwrap();
+    mint(&contract, deps.as_mut(), "grow".into(), owner.clone());
+
+    // wallets sign the sha256 hash of the ADR-036 sign doc, with their own address as signer
+    let sign = |key: &SigningKey, permit: &Permit| -> Binary {
+        let signer = signer_address(&key.verifying_key().to_bytes(), "cosmos").unwrap();
+        let signature: Signature = key.sign(&permit.sign_doc(&signer).unwrap());
+        Binary::from(signature.as_ref())
+    };
+    let permit = Permit {
+        spender: "bella".to_string(),
+        token_id: "grow".to_string(),
+        expires: Expiration::AtHeight(env.block.height + 100),
+        nonce: 0,
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+    };
+    let permit_msg = |permit: &Permit, signature: Binary| ExecuteMsg::ApproveWithPermit {
+        permit: permit.clone(),
+        pubkey: pubkey.clone(),
+        signature,
+    };
+    let invalid = |reason: &str| ContractError::InvalidPermit {
+        reason: reason.to_string(),
+    };
+
+    // which is what signArbitrary signs for the compact JSON of the permit
+    let data = r#"{"spender":"bella","token_id":"grow","expires":{"at_height":12445},"nonce":0,"chain_id":"cosmos-testnet-14002","contract":"cosmos2contract"}"#;
+    assert_eq!(to_vec(&permit).unwrap(), data.as_bytes());
+    let sign_doc = format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        Binary::from(data.as_bytes()).to_base64(),
+        owner
+    );
+    assert_eq!(permit.sign_doc(&owner).unwrap(), sign_doc.as_bytes());
+
+    // anyone can relay the permit
+    let relayer = mock_info("relayer", &[]);
+    let signed = permit_msg(&permit, sign(&key, &permit));
+    contract
+        .execute(deps.as_mut(), env.clone(), relayer.clone(), signed.clone())
+        .unwrap();
+    let res = contract
+        .approval(
+            deps.as_ref(),
+            env.clone(),
+            "grow".into(),
+            "bella".into(),
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.approval.spender, "bella");
+    let res = contract.permit_nonce(deps.as_ref(), owner.clone()).unwrap();
+    assert_eq!(res.nonce, 1);
+
+    // but only once
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), relayer.clone(), signed)
+        .unwrap_err();
+    assert_eq!(err, invalid("wrong nonce"));
+
+    // the permit cannot be changed after signing
+    let next = Permit { nonce: 1, ..permit };
+    let signature = sign(&key, &next);
+    let changed = Permit {
+        spender: "chris".to_string(),
+        ..next.clone()
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            permit_msg(&changed, signature),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("wrong signature"));
+
+    // nor replayed on another contract
+    let elsewhere = Permit {
+        contract: "other".to_string(),
+        ..next.clone()
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            permit_msg(&elsewhere, sign(&key, &elsewhere)),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("signed for another chain or contract"));
+
+    // nor signed to last forever
+    let forever = Permit {
+        expires: Expiration::Never {},
+        ..next.clone()
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            relayer.clone(),
+            permit_msg(&forever, sign(&key, &forever)),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("must expire"));
+
+    // someone else's key does not grant anything
+    let stranger = SigningKey::from_bytes(&[2; 32]).unwrap();
+    let stranger_permit = Permit { nonce: 0, ..next };
+    let msg = ExecuteMsg::ApproveWithPermit {
+        permit: stranger_permit.clone(),
+        pubkey: Binary::from(stranger.verifying_key().to_bytes().to_vec()),
+        signature: sign(&stranger, &stranger_permit),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env, relayer, msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn mint_with_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    // the voucher signer's account takes the prefix of the contract address
+    let mut env = mock_env();
+    env.contract.address =
+        Addr::unchecked("cosmos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9s4hmalr");
+    let invalid = |reason: &str| ContractError::InvalidVoucher {
+        reason: reason.to_string(),
+    };
+
+    let key = SigningKey::from_bytes(&[3; 32]).unwrap();
+    let signer = signer_address(&key.verifying_key().to_bytes(), "cosmos").unwrap();
+    let sign = |voucher: &Voucher<Extension>| -> Binary {
+        // the signer's wallet signs the sha256 hash of the ADR-036 sign doc
+        let signature: Signature = key.sign(&voucher.sign_doc(&signer).unwrap());
+        Binary::from(signature.as_ref())
+    };
+    let voucher = Voucher {
+        token_id: "sunrise".to_string(),
+        token_uri: Some("https://example.com/sunrise.json".to_string()),
+        extension: None,
+        price: Some(coin(100, "ustars")),
+        expires: Some(Expiration::AtHeight(env.block.height + 10)),
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+    };
+    let redeem = |voucher: &Voucher<Extension>, signature: Binary| ExecuteMsg::MintWithVoucher {
+        voucher: voucher.clone(),
+        signature,
+    };
+    let buyer = mock_info("bella", &coins(100, "ustars"));
+
+    // nothing can be redeemed before the minter registers their key
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer.clone(),
+            redeem(&voucher, sign(&voucher)),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("no voucher signer registered"));
+    let pubkey = Binary::from(key.verifying_key().to_bytes().to_vec());
+    let register = ExecuteMsg::SetVoucherSigner {
+        pubkey: Some(pubkey.clone()),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), buyer.clone(), register.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), env.clone(), mock_info(MINTER, &[]), register)
+        .unwrap();
+    let res = contract.voucher_signer(deps.as_ref()).unwrap();
+    assert_eq!(res.pubkey, Some(pubkey));
+
+    // the price must be paid in full
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bella", &coins(99, "ustars")),
+            redeem(&voucher, sign(&voucher)),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::WrongPayment {
+            price: "100ustars".to_string()
+        }
+    );
+
+    // the buyer gets the token and the minter the payment
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer.clone(),
+            redeem(&voucher, sign(&voucher)),
+        )
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: MINTER.to_string(),
+            amount: coins(100, "ustars"),
+        })]
+    );
+    let owner = contract
+        .owner_of(deps.as_ref(), env.clone(), "sunrise".into(), false)
+        .unwrap();
+    assert_eq!(owner.owner, "bella");
+
+    // a voucher is used up, even once its token is burned
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "sunrise".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), buyer.clone(), burn_msg)
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer.clone(),
+            redeem(&voucher, sign(&voucher)),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("already redeemed"));
+
+    // vouchers cannot be changed after signing
+    let dawn = Voucher {
+        token_id: "dawn".to_string(),
+        ..voucher
+    };
+    let free = Voucher {
+        price: None,
+        ..dawn.clone()
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bella", &[]),
+            redeem(&free, sign(&dawn)),
+        )
+        .unwrap_err();
+    assert_eq!(err, invalid("wrong signature"));
+
+    // nor used after they expired
+    let mut later = env.clone();
+    later.block.height += 10;
+    let err = contract
+        .execute(deps.as_mut(), later, buyer, redeem(&dawn, sign(&dawn)))
+        .unwrap_err();
+    assert_eq!(err, invalid("expired"));
+}
+
+#[test]
+fn minter_roles() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let mint_as = |deps: DepsMut, env: Env, sender: &str, token_id: &str| {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: Some(token_id.to_string()),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+            non_transferable: None,
+            lock_until: None,
+        });
+        contract.execute(deps, env, mock_info(sender, &[]), mint_msg)
+    };
+
+    // only the admin manages roles
+    let add_sales = ExecuteMsg::AddMinter {
+        minter: String::from("sales"),
+        quota: Some(2),
+        expires: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER, &[]),
+            add_sales.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let admin = mock_info(ADMIN, &[]);
+    contract
+        .execute(deps.as_mut(), env.clone(), admin.clone(), add_sales)
+        .unwrap();
+    let add_service = ExecuteMsg::AddMinter {
+        minter: String::from("service"),
+        quota: None,
+        expires: Some(Expiration::AtHeight(env.block.height + 5)),
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), admin.clone(), add_service)
+        .unwrap();
+
+    // quotas are used up one token at a time
+    mint_as(deps.as_mut(), env.clone(), "sales", "1").unwrap();
+    mint_as(deps.as_mut(), env.clone(), "sales", "2").unwrap();
+    let err = mint_as(deps.as_mut(), env.clone(), "sales", "3").unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::QuotaExhausted {
+            minter: String::from("sales")
+        }
+    );
+    let res = contract.minters(deps.as_ref(), None, None, None).unwrap();
+    assert_eq!(
+        res.minters,
+        vec![
+            MinterRole {
+                address: String::from("sales"),
+                quota: Some(0),
+                expires: None,
+            },
+            MinterRole {
+                address: String::from("service"),
+                quota: None,
+                expires: Some(Expiration::AtHeight(env.block.height + 5)),
+            },
+        ]
+    );
+    let res = contract
+        .minters(deps.as_ref(), Some(String::from("sales")), Some(1), None)
+        .unwrap();
+    assert_eq!(res.minters.len(), 1);
+    assert_eq!(res.minters[0].address, "service");
+
+    // roles end when they expire or are removed
+    mint_as(deps.as_mut(), env.clone(), "service", "4").unwrap();
+    let mut later = env.clone();
+    later.block.height += 5;
+    let err = mint_as(deps.as_mut(), later, "service", "5").unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let remove_msg = ExecuteMsg::RemoveMinter {
+        minter: String::from("service"),
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), admin, remove_msg)
+        .unwrap();
+    let err = mint_as(deps.as_mut(), env.clone(), "service", "5").unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the minter itself is not limited
+    mint_as(deps.as_mut(), env, MINTER, "5").unwrap();
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!(count.count, 4);
+}
+
+// helper functions for testing
+fn mint(
+    contract: &Cw721Contract<'static, Extension, Empty>,
+    deps: DepsMut<'_>,
+    token_id: String,
+    owner: String,
+) -> Response {
+    let token_uri = format!("{}{}", "https://lunapunks.io/", token_id);
+
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: Some(token_id),
+        owner,
+        token_uri: Some(token_uri),
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    });
+
+    let allowed = mock_info(MINTER, &[]);
+    contract
+        .execute(deps, mock_env(), allowed, mint_msg)
+        .unwrap()
 }
@@ -1,4 +1,7 @@
 use cosmwasm_std::StdError;
+use cw721::Expiration;
+
+use crate::msg::Operation;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -12,9 +15,76 @@ pub enum ContractError {
     #[error("token_id already claimed")]
     Claimed {},
 
+    #[error("Minting quota of {minter} is used up")]
+    QuotaExhausted { minter: String },
+
+    #[error("Max supply of {max_supply} tokens reached")]
+    MaxSupplyReached { max_supply: u64 },
+
+    #[error("Invalid token_id: {reason}")]
+    InvalidTokenId { reason: String },
+
+    #[error("Invalid voucher: {reason}")]
+    InvalidVoucher { reason: String },
+
+    #[error("Voucher must be paid with exactly {price}")]
+    WrongPayment { price: String },
+
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
+    #[error("Token cannot have more than {max} approvals")]
+    TooManyApprovals { max: u32 },
+
+    #[error("Invalid permit: {reason}")]
+    InvalidPermit { reason: String },
+
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
+
+    #[error("Token is non-transferable")]
+    NonTransferable {},
+
+    #[error("Contract is paused for {operation:?}")]
+    Paused { operation: Operation },
+
+    #[error("Transfer rejected by {hook}: {reason}")]
+    TransferRejected { hook: String, reason: String },
+
+    #[error("{contract} is not a contract")]
+    NotAContract { contract: String },
+
+    #[error("Receiver {contract} rejected token {token_id}: {error}")]
+    ReceiverFailed {
+        contract: String,
+        token_id: String,
+        error: String,
+    },
+
+    #[error("Cannot migrate from {contract}")]
+    WrongContract { contract: String },
+
+    #[error("Cannot migrate from version {from} down to {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("Cannot handle more than {max} tokens at once")]
+    TooManyTokens { max: u32 },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Token {token_id} is locked until {lock_until}")]
+    Locked {
+        token_id: String,
+        lock_until: Expiration,
+    },
+
+    #[error("No sequential token IDs left")]
+    TokenIdsExhausted {},
+
+    #[error("Token {token_id} is already locked until {lock_until}, locks can only be extended")]
+    LockShortened {
+        token_id: String,
+        lock_until: Expiration,
+    },
 }
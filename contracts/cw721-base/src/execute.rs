@@ -1,19 +1,48 @@
+use bech32::{ToBase32, Variant};
+use ripemd160::Ripemd160;
+use semver::Version;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
 
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, ContractInfoResponse as WasmContractInfo, Deps, DepsMut,
+    Empty, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    WasmQuery,
+};
 
-use cw2::set_contract_version;
-use cw721::{ContractInfoResponse, CustomMsg, Cw721Execute, Cw721ReceiveMsg, Expiration};
+use cw2::{get_contract_version, set_contract_version};
+use cw3::Cw3QueryMsg;
+use cw721::{
+    CheckTransferResponse, ContractInfoResponse, CustomMsg, Cw721Event, Cw721Execute, Cw721HookMsg,
+    Cw721ReceiveMsg, Cw721TransferCheck, Expiration,
+};
+use cw_storage_plus::Bound;
+use cw_utils::{maybe_addr, ThresholdResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+#[cfg(feature = "provenance")]
+use crate::msg::ProvenanceAction;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, MintMsg, MintResponse, Operation, Permit, Voucher,
+    DEFAULT_MAX_TOKEN_ID_LENGTH,
+};
+use crate::state::{
+    numeric_token_id, Approval, Cw721Contract, MinterConfig, PendingSend, SoulboundConfig,
+    SubscriberConfig, TokenInfo,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw721-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const SUBSCRIBER_REPLY_ID: u64 = 1;
+const SAFE_SEND_REPLY_ID: u64 = 2;
+
+const DEFAULT_PRUNE_LIMIT: u32 = 10;
+const MAX_PRUNE_LIMIT: u32 = 30;
+
 impl<'a, T, C> Cw721Contract<'a, T, C>
 where
     T: Serialize + DeserializeOwned + Clone,
@@ -35,9 +64,141 @@ where
         self.contract_info.save(deps.storage, &info)?;
         let minter = deps.api.addr_validate(&msg.minter)?;
         self.minter.save(deps.storage, &minter)?;
+        if let Some(admin) = msg.admin {
+            let admin = deps.api.addr_validate(&admin)?;
+            self.admin.save(deps.storage, &admin)?;
+        }
+        if let Some(governance) = msg.governance {
+            let governance = validate_governance(deps.as_ref(), &governance)?;
+            self.governance.save(deps.storage, &governance)?;
+        }
+
+        let soulbound = SoulboundConfig {
+            non_transferable: msg.non_transferable.unwrap_or(false),
+            revocable: msg.revocable.unwrap_or(false),
+        };
+        self.soulbound.save(deps.storage, &soulbound)?;
+        self.snapshots
+            .save(deps.storage, &msg.snapshots.unwrap_or(false))?;
+        if let Some(rules) = msg.token_id_rules {
+            // reject a malformed charset right away rather than on every mint
+            rules.char_ranges()?;
+            self.token_id_rules.save(deps.storage, &rules)?;
+        }
+        if let Some(max_supply) = msg.max_supply {
+            self.max_supply.save(deps.storage, &max_supply)?;
+        }
+        if let Some(max_approvals) = msg.max_approvals {
+            self.max_approvals.save(deps.storage, &max_approvals)?;
+        }
         Ok(Response::default())
     }
 
+    /// Backfills the owner balances and count of collections deployed before they were tracked.
+    /// Only cw721-base contracts of this version or older can be migrated. The backfill reads
+    /// every token and operator in this one transaction, so collections too large for the
+    /// block gas limit cannot be migrated
+    pub fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response<C>, ContractError> {
+        let stored = get_contract_version(deps.storage)?;
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::WrongContract {
+                contract: stored.contract,
+            });
+        }
+        let parse = |version: &str| {
+            version.parse::<Version>().map_err(|err| {
+                StdError::generic_err(format!("Invalid version {}: {}", version, err))
+            })
+        };
+        if parse(&stored.version)? > parse(CONTRACT_VERSION)? {
+            return Err(ContractError::CannotDowngrade {
+                from: stored.version,
+                to: CONTRACT_VERSION.to_string(),
+            });
+        }
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        // the owner index lists tokens grouped by owner
+        let mut balances: Vec<(Addr, u64)> = vec![];
+        for item in self
+            .tokens
+            .idx
+            .owner
+            .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (_, token) = item?;
+            match balances.last_mut() {
+                Some((owner, balance)) if *owner == token.owner => *balance += 1,
+                _ => balances.push((token.owner, 1)),
+            }
+        }
+        for (owner, balance) in &balances {
+            self.balances.save(deps.storage, owner, balance)?;
+        }
+        self.num_owners
+            .save(deps.storage, &(balances.len() as u64))?;
+
+        // tokens minted before the mint order was tracked are ordered by ID,
+        // shortest first so numeric IDs come out in numeric order
+        if self.mint_count.may_load(deps.storage)?.is_none() {
+            let mut tokens = self
+                .tokens
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            tokens.sort_by(|(a, _), (b, _)| (a.len(), a).cmp(&(b.len(), b)));
+            for (token_id, mut token) in tokens {
+                token.mint_seq = self.increment_mint_count(deps.storage)?;
+                self.tokens.save(deps.storage, &token_id, &token)?;
+            }
+        }
+
+        // sequential IDs continue after the highest numeric one. Numeric IDs that were burned
+        // before, and are higher than any left, cannot be known and may be assigned again
+        let mut highest = None;
+        for token_id in self.tokens.keys(deps.storage, None, None, Order::Ascending) {
+            highest = highest.max(numeric_token_id(&token_id?));
+        }
+        if let Some(highest) = highest {
+            let next = self
+                .next_token_id
+                .may_load(deps.storage)?
+                .unwrap_or_default();
+            // the last u64 leaves no sequential ID, auto-assigning then fails instead
+            self.next_token_id
+                .save(deps.storage, &next.max(highest.saturating_add(1)))?;
+        }
+
+        let approvals = self
+            .tokens
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (token_id, token) in approvals {
+            for approval in token.approvals {
+                self.spender_approvals.save(
+                    deps.storage,
+                    (&approval.spender, &token_id),
+                    &approval.expires,
+                )?;
+            }
+        }
+
+        let operators = self
+            .operators
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for ((granter, operator), expires) in operators {
+            self.operator_grants
+                .save(deps.storage, (&operator, &granter), &expires)?;
+        }
+
+        Ok(Response::new().add_attribute("action", "migrate"))
+    }
+
     pub fn execute(
         &self,
         deps: DepsMut,
@@ -45,13 +206,30 @@ where
         info: MessageInfo,
         msg: ExecuteMsg<T>,
     ) -> Result<Response<C>, ContractError> {
+        if let Some(operation) = msg.operation() {
+            if self.pause_state(deps.storage)?.is_paused(operation) {
+                return Err(ContractError::Paused { operation });
+            }
+        }
+
         match msg {
             ExecuteMsg::Mint(msg) => self.mint(deps, env, info, msg),
+            ExecuteMsg::MintWithVoucher { voucher, signature } => {
+                self.mint_with_voucher(deps, env, info, voucher, signature)
+            }
+            ExecuteMsg::SetVoucherSigner { pubkey } => {
+                self.set_voucher_signer(deps, env, info, pubkey)
+            }
             ExecuteMsg::Approve {
                 spender,
                 token_id,
                 expires,
             } => self.approve(deps, env, info, spender, token_id, expires),
+            ExecuteMsg::ApproveWithPermit {
+                permit,
+                pubkey,
+                signature,
+            } => self.approve_with_permit(deps, env, info, permit, pubkey, signature),
             ExecuteMsg::Revoke { spender, token_id } => {
                 self.revoke(deps, env, info, spender, token_id)
             }
@@ -59,6 +237,17 @@ where
                 self.approve_all(deps, env, info, operator, expires)
             }
             ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
+            ExecuteMsg::RevokeAllApprovals { token_id } => {
+                self.revoke_all_approvals(deps, env, info, token_id)
+            }
+            ExecuteMsg::PruneExpired {
+                owner,
+                start_after,
+                limit,
+            } => self.prune_expired(deps, env, info, owner, start_after, limit),
+            ExecuteMsg::PruneTokenApprovals { token_ids } => {
+                self.prune_token_approvals(deps, env, info, token_ids)
+            }
             ExecuteMsg::TransferNft {
                 recipient,
                 token_id,
@@ -68,7 +257,84 @@ where
                 token_id,
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
+            ExecuteMsg::SafeSendNft {
+                contract,
+                token_id,
+                msg,
+            } => self.safe_send_nft(deps, env, info, contract, token_id, msg),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
+            ExecuteMsg::UpdateAdmin { admin } => self.update_admin(deps, env, info, admin),
+            ExecuteMsg::UpdateGovernance { governance } => {
+                self.update_governance(deps, env, info, governance)
+            }
+            ExecuteMsg::Pause { operations } => self.set_paused(deps, env, info, operations, true),
+            ExecuteMsg::Unpause { operations } => {
+                self.set_paused(deps, env, info, operations, false)
+            }
+            ExecuteMsg::SetMaxApprovals { max_approvals } => {
+                self.set_max_approvals(deps, env, info, max_approvals)
+            }
+            ExecuteMsg::AddMinter {
+                minter,
+                quota,
+                expires,
+            } => self.add_minter(deps, env, info, minter, quota, expires),
+            ExecuteMsg::RemoveMinter { minter } => self.remove_minter(deps, env, info, minter),
+            ExecuteMsg::AddLocker { locker } => self.add_locker(deps, env, info, locker),
+            ExecuteMsg::RemoveLocker { locker } => self.remove_locker(deps, env, info, locker),
+            ExecuteMsg::LockToken {
+                token_id,
+                lock_until,
+            } => self.lock_token(deps, env, info, token_id, lock_until),
+            ExecuteMsg::AddTransferHook { hook } => self.add_transfer_hook(deps, env, info, hook),
+            ExecuteMsg::RemoveTransferHook { hook } => {
+                self.remove_transfer_hook(deps, env, info, hook)
+            }
+            ExecuteMsg::AddSubscriber {
+                subscriber,
+                fail_open,
+            } => self.add_subscriber(deps, env, info, subscriber, fail_open),
+            ExecuteMsg::RemoveSubscriber { subscriber } => {
+                self.remove_subscriber(deps, env, info, subscriber)
+            }
+            #[cfg(feature = "provenance")]
+            ExecuteMsg::SetHistoryRetention { max_entries } => {
+                self.set_history_retention(deps, env, info, max_entries)
+            }
+        }
+    }
+
+    /// Fail-open subscribers only reply on error: the failure is logged and dropped.
+    /// Receivers of a safe send always reply: a failure reverts the whole send
+    pub fn reply(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        msg: Reply,
+    ) -> Result<Response<C>, ContractError> {
+        match msg.id {
+            SUBSCRIBER_REPLY_ID => match msg.result.into_result() {
+                // only expected with reply_always, there is nothing to log then
+                Ok(_) => Ok(Response::new()),
+                Err(error) => Ok(Response::new()
+                    .add_attribute("action", "subscriber_failed")
+                    .add_attribute("error", error)),
+            },
+            SAFE_SEND_REPLY_ID => {
+                let send = self.pop_pending_send(deps.storage)?;
+                match msg.result.into_result() {
+                    Ok(_) => Ok(Response::new()
+                        .add_attribute("action", "safe_send_received")
+                        .add_attribute("recipient", send.contract)
+                        .add_attribute("token_id", send.token_id)),
+                    Err(error) => Err(ContractError::ReceiverFailed {
+                        contract: send.contract.into_string(),
+                        token_id: send.token_id,
+                        error,
+                    }),
+                }
+            }
+            id => Err(ContractError::UnknownReplyId { id }),
         }
     }
 }
@@ -82,35 +348,632 @@ where
     pub fn mint(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         msg: MintMsg<T>,
     ) -> Result<Response<C>, ContractError> {
-        let minter = self.minter.load(deps.storage)?;
+        if self.check_minter(deps.as_ref(), &info).is_err() {
+            self.use_minter_role(deps.storage, &env, &info.sender)?;
+        }
+
+        self._mint(deps, &env, &info, msg)
+    }
+
+    pub fn mint_with_voucher(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        voucher: Voucher<T>,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        let invalid = |reason: &str| ContractError::InvalidVoucher {
+            reason: reason.to_string(),
+        };
+        let pubkey = self
+            .voucher_signer
+            .may_load(deps.storage)?
+            .ok_or_else(|| invalid("no voucher signer registered"))?;
+        if voucher.chain_id != env.block.chain_id || voucher.contract != env.contract.address {
+            return Err(invalid("signed for another chain or contract"));
+        }
+        if let Some(expires) = voucher.expires {
+            if expires.is_expired(&env.block) {
+                return Err(invalid("expired"));
+            }
+        }
+        // also covers vouchers of burned tokens, which could be minted again otherwise
+        if self.redeemed_vouchers.has(deps.storage, &voucher.token_id) {
+            return Err(invalid("already redeemed"));
+        }
+        // signed by the signer's account on this chain
+        let signer = signer_address(&pubkey, bech32_prefix(env.contract.address.as_str()))
+            .map_err(|_| invalid("malformed signer pubkey"))?;
+        let sign_doc = voucher.sign_doc(&signer)?;
+        if !deps
+            .api
+            .secp256k1_verify(&Sha256::digest(&sign_doc), &signature, &pubkey)
+            .map_err(|_| invalid("malformed signature"))?
+        {
+            return Err(invalid("wrong signature"));
+        }
+        if info.funds != voucher.price.clone().into_iter().collect::<Vec<_>>() {
+            return Err(ContractError::WrongPayment {
+                price: voucher
+                    .price
+                    .map_or_else(|| "nothing".to_string(), |price| price.to_string()),
+            });
+        }
+        self.redeemed_vouchers
+            .save(deps.storage, &voucher.token_id, &Empty {})?;
+
+        // the buyer gets the token, the creator the payment
+        let creator = self.minter.load(deps.storage)?;
+        let msg = MintMsg {
+            token_id: Some(voucher.token_id),
+            owner: info.sender.to_string(),
+            token_uri: voucher.token_uri,
+            extension: voucher.extension,
+            non_transferable: None,
+            lock_until: None,
+        };
+        let res = self._mint(deps, &env, &info, msg)?;
+        Ok(match voucher.price {
+            Some(price) => res.add_message(BankMsg::Send {
+                to_address: creator.into_string(),
+                amount: vec![price],
+            }),
+            None => res,
+        })
+    }
+
+    pub fn set_voucher_signer(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        pubkey: Option<Binary>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_minter(deps.as_ref(), &info)?;
+
+        match &pubkey {
+            // compressed secp256k1 key, as vouchers are signed by the key's account
+            Some(pubkey) if pubkey.len() == 33 => self.voucher_signer.save(deps.storage, pubkey)?,
+            Some(_) => {
+                return Err(ContractError::InvalidVoucher {
+                    reason: "malformed signer pubkey".to_string(),
+                })
+            }
+            None => self.voucher_signer.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "set_voucher_signer")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "pubkey",
+                pubkey.map_or_else(|| "none".to_string(), |pubkey| pubkey.to_base64()),
+            ))
+    }
+
+    pub fn update_governance(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        governance: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let governance = governance
+            .map(|g| validate_governance(deps.as_ref(), &g))
+            .transpose()?;
+        match &governance {
+            Some(addr) => {
+                self.governance.save(deps.storage, addr)?;
+                // minter roles and the voucher signer would bypass governance otherwise,
+                // it has to grant them again through proposals
+                let minters = self
+                    .minters
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()?;
+                for minter in minters {
+                    self.minters.remove(deps.storage, &minter);
+                }
+                self.voucher_signer.remove(deps.storage);
+            }
+            None => self.governance.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_governance")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "governance",
+                governance
+                    .map(Addr::into_string)
+                    .unwrap_or_else(|| "none".to_string()),
+            ))
+    }
+
+    pub fn update_admin(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        admin: Option<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let admin = admin.map(|a| deps.api.addr_validate(&a)).transpose()?;
+        match &admin {
+            Some(addr) => self.admin.save(deps.storage, addr)?,
+            None => self.admin.remove(deps.storage),
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_admin")
+            .add_attribute("sender", info.sender)
+            .add_attribute(
+                "admin",
+                admin
+                    .map(Addr::into_string)
+                    .unwrap_or_else(|| "none".to_string()),
+            ))
+    }
+
+    pub fn set_max_approvals(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        max_approvals: u32,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        self.max_approvals.save(deps.storage, &max_approvals)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_max_approvals")
+            .add_attribute("sender", info.sender)
+            .add_attribute("max_approvals", max_approvals.to_string()))
+    }
+
+    pub fn set_paused(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operations: Option<Vec<Operation>>,
+        paused: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let mut state = self.pause_state(deps.storage)?;
+        for operation in operations.unwrap_or_else(Operation::all) {
+            state.set(operation, paused);
+        }
+        self.pause.save(deps.storage, &state)?;
+
+        let action = if paused { "pause" } else { "unpause" };
+        Ok(Response::new()
+            .add_attribute("action", action)
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn add_minter(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        minter: String,
+        quota: Option<u64>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
 
-        if info.sender != minter {
+        if let Some(expires) = expires {
+            if expires.is_expired(&env.block) {
+                return Err(ContractError::Expired {});
+            }
+        }
+        let minter_addr = deps.api.addr_validate(&minter)?;
+        self.minters
+            .save(deps.storage, &minter_addr, &MinterConfig { quota, expires })?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_minter")
+            .add_attribute("sender", info.sender)
+            .add_attribute("minter", minter))
+    }
+
+    pub fn remove_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        minter: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let minter_addr = deps.api.addr_validate(&minter)?;
+        self.minters.remove(deps.storage, &minter_addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_minter")
+            .add_attribute("sender", info.sender)
+            .add_attribute("minter", minter))
+    }
+
+    pub fn add_locker(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        locker: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let locker_addr = deps.api.addr_validate(&locker)?;
+        self.lockers.save(deps.storage, &locker_addr, &Empty {})?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_locker")
+            .add_attribute("sender", info.sender)
+            .add_attribute("locker", locker))
+    }
+
+    pub fn remove_locker(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        locker: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let locker_addr = deps.api.addr_validate(&locker)?;
+        self.lockers.remove(deps.storage, &locker_addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_locker")
+            .add_attribute("sender", info.sender)
+            .add_attribute("locker", locker))
+    }
+
+    pub fn add_transfer_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let hook_addr = deps.api.addr_validate(&hook)?;
+        self.transfer_hooks
+            .save(deps.storage, &hook_addr, &Empty {})?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_transfer_hook")
+            .add_attribute("sender", info.sender)
+            .add_attribute("hook", hook))
+    }
+
+    pub fn remove_transfer_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hook: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let hook_addr = deps.api.addr_validate(&hook)?;
+        self.transfer_hooks.remove(deps.storage, &hook_addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_transfer_hook")
+            .add_attribute("sender", info.sender)
+            .add_attribute("hook", hook))
+    }
+
+    pub fn add_subscriber(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        subscriber: String,
+        fail_open: Option<bool>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let subscriber_addr = deps.api.addr_validate(&subscriber)?;
+        let config = SubscriberConfig {
+            fail_open: fail_open.unwrap_or(false),
+        };
+        self.subscribers
+            .save(deps.storage, &subscriber_addr, &config)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_subscriber")
+            .add_attribute("sender", info.sender)
+            .add_attribute("subscriber", subscriber)
+            .add_attribute("fail_open", config.fail_open.to_string()))
+    }
+
+    pub fn remove_subscriber(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        subscriber: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let subscriber_addr = deps.api.addr_validate(&subscriber)?;
+        self.subscribers.remove(deps.storage, &subscriber_addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_subscriber")
+            .add_attribute("sender", info.sender)
+            .add_attribute("subscriber", subscriber))
+    }
+
+    pub fn lock_token(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        lock_until: Expiration,
+    ) -> Result<Response<C>, ContractError> {
+        if !self.lockers.has(deps.storage, &info.sender) {
             return Err(ContractError::Unauthorized {});
         }
+        // reject expired data as invalid
+        if lock_until.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
 
-        // create the token
-        let token = TokenInfo {
-            owner: deps.api.addr_validate(&msg.owner)?,
-            approvals: vec![],
-            token_uri: msg.token_uri,
-            extension: msg.extension,
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        // locks can only be extended, or a locker could release a vesting token early
+        if let Some(current) = token.active_lock(&env.block) {
+            if !matches!(
+                lock_until.partial_cmp(&current),
+                Some(Ordering::Equal | Ordering::Greater)
+            ) {
+                return Err(ContractError::LockShortened {
+                    token_id,
+                    lock_until: current,
+                });
+            }
+        }
+        token.lock_until = Some(lock_until);
+        self.tokens.save(deps.storage, &token_id, &token)?;
+        self.locked_tokens
+            .save(deps.storage, (&token.owner, &token_id), &lock_until)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "lock_token")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("lock_until", lock_until.to_string()))
+    }
+
+    pub fn approve_with_permit(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        permit: Permit,
+        pubkey: Binary,
+        signature: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        let invalid = |reason: &str| ContractError::InvalidPermit {
+            reason: reason.to_string(),
         };
-        self.tokens
-            .update(deps.storage, &msg.token_id, |old| match old {
-                Some(_) => Err(ContractError::Claimed {}),
-                None => Ok(token),
-            })?;
+        if permit.chain_id != env.block.chain_id || permit.contract != env.contract.address {
+            return Err(invalid("signed for another chain or contract"));
+        }
+        // a leaked permit could be used at any time otherwise, until its nonce is spent
+        if let Expiration::Never {} = permit.expires {
+            return Err(invalid("must expire"));
+        }
 
-        self.increment_tokens(deps.storage)?;
+        // the signer is approving, as if they had sent the message themselves
+        let owner = self.tokens.load(deps.storage, &permit.token_id)?.owner;
+        let signer = deps
+            .api
+            .addr_validate(&signer_address(&pubkey, bech32_prefix(owner.as_str()))?)?;
+        if permit.nonce != self.next_permit_nonce(deps.storage, &signer)? {
+            return Err(invalid("wrong nonce"));
+        }
+        let sign_doc = permit.sign_doc(signer.as_str())?;
+        if !deps
+            .api
+            .secp256k1_verify(&Sha256::digest(&sign_doc), &signature, &pubkey)
+            .map_err(|_| invalid("malformed signature or pubkey"))?
+        {
+            return Err(invalid("wrong signature"));
+        }
+        self.permit_nonces
+            .save(deps.storage, &signer, &(permit.nonce + 1))?;
+
+        let signed = MessageInfo {
+            sender: signer,
+            funds: vec![],
+        };
+        let token = self._update_approvals(
+            deps,
+            &env,
+            &signed,
+            &permit.spender,
+            &permit.token_id,
+            true,
+            Some(permit.expires),
+        )?;
 
+        let event = Cw721Event::approve(
+            &signed.sender,
+            token.owner,
+            &permit.spender,
+            &permit.token_id,
+            token.token_uri,
+        );
         Ok(Response::new()
-            .add_attribute("action", "mint")
-            .add_attribute("minter", info.sender)
-            .add_attribute("token_id", msg.token_id))
+            .add_event(event.into())
+            .add_attribute("action", "approve_with_permit")
+            .add_attribute("sender", info.sender)
+            .add_attribute("signer", signed.sender)
+            .add_attribute("spender", permit.spender)
+            .add_attribute("token_id", permit.token_id))
+    }
+
+    pub fn revoke_all_approvals(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        self.check_can_approve(deps.as_ref(), &env, &info, &token)?;
+
+        let approvals = std::mem::take(&mut token.approvals);
+        self.clear_approvals(deps.storage, &token_id, &approvals);
+        self.tokens.save(deps.storage, &token_id, &token)?;
+
+        let events = approvals.iter().map(|apr| {
+            Cw721Event::revoke(
+                &info.sender,
+                &token.owner,
+                &apr.spender,
+                &token_id,
+                token.token_uri.clone(),
+            )
+            .into()
+        });
+        Ok(Response::new()
+            .add_events(events)
+            .add_attribute("action", "revoke_all_approvals")
+            .add_attribute("sender", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn prune_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<Response<C>, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT).min(MAX_PRUNE_LIMIT) as usize;
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        // bound the operators read, not the ones pruned, as most may still be live
+        let scanned = self
+            .operators
+            .prefix(&owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut pruned = 0;
+        for (operator, expires) in &scanned {
+            if expires.is_expired(&env.block) {
+                self.operators.remove(deps.storage, (&owner_addr, operator));
+                self.operator_grants
+                    .remove(deps.storage, (operator, &owner_addr));
+                pruned += 1;
+            }
+        }
+
+        let mut res = Response::new()
+            .add_attribute("action", "prune_expired")
+            .add_attribute("sender", info.sender)
+            .add_attribute("owner", owner)
+            .add_attribute("pruned", pruned.to_string());
+        // there may be more to read from there
+        if scanned.len() == limit {
+            if let Some((operator, _)) = scanned.last() {
+                res = res.add_attribute("next_start_after", operator);
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn prune_token_approvals(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        if token_ids.len() > MAX_PRUNE_LIMIT as usize {
+            return Err(ContractError::TooManyTokens {
+                max: MAX_PRUNE_LIMIT,
+            });
+        }
+
+        let mut pruned = 0;
+        for token_id in &token_ids {
+            // burned tokens have nothing left to prune
+            if let Some(mut token) = self.tokens.may_load(deps.storage, token_id)? {
+                let expired = self.drop_expired_approvals(deps.storage, &env, token_id, &mut token);
+                if expired > 0 {
+                    self.tokens.save(deps.storage, token_id, &token)?;
+                    pruned += expired;
+                }
+            }
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "prune_token_approvals")
+            .add_attribute("sender", info.sender)
+            .add_attribute("pruned", pruned.to_string()))
+    }
+
+    pub fn safe_send_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    ) -> Result<Response<C>, ContractError> {
+        // accounts have no contract info, so this fails for anything but a contract
+        let contract_addr = deps.api.addr_validate(&contract)?;
+        let query = WasmQuery::ContractInfo {
+            contract_addr: contract.clone(),
+        };
+        deps.querier
+            .query::<WasmContractInfo>(&query.into())
+            .map_err(|_| ContractError::NotAContract {
+                contract: contract.clone(),
+            })?;
+
+        self.push_pending_send(
+            deps.storage,
+            PendingSend {
+                contract: contract_addr,
+                token_id: token_id.clone(),
+            },
+        )?;
+        let send = Cw721ReceiveMsg {
+            sender: info.sender.to_string(),
+            token_id: token_id.clone(),
+            msg,
+        };
+        let delivery = SubMsg::reply_always(
+            send.into_cosmos_msg_with_funds(contract.clone(), info.funds.clone())?,
+            SAFE_SEND_REPLY_ID,
+        );
+        self._send_nft(deps, env, info, contract, token_id, delivery)
     }
 }
 
@@ -123,15 +986,33 @@ where
 
     fn transfer_nft(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         recipient: String,
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
-        self._transfer_nft(deps, &env, &info, &recipient, &token_id)?;
+        let from = self.tokens.load(deps.storage, &token_id)?.owner;
+        let token = self._transfer_nft(deps.branch(), &env, &info, &recipient, &token_id)?;
+        #[cfg(feature = "provenance")]
+        self.provenance.record(
+            deps.storage,
+            &env.block,
+            &token_id,
+            &token.owner,
+            ProvenanceAction::Transfer,
+        )?;
 
+        let event =
+            Cw721Event::transfer(&info.sender, &from, &recipient, &token_id, token.token_uri);
+        let transferred = Cw721HookMsg::Transferred {
+            from: from.into_string(),
+            to: recipient.clone(),
+            token_id: token_id.clone(),
+        };
         Ok(Response::new()
+            .add_submessages(self.subscriber_msgs(deps.storage, transferred)?)
+            .add_event(event.into())
             .add_attribute("action", "transfer_nft")
             .add_attribute("sender", info.sender)
             .add_attribute("recipient", recipient)
@@ -147,22 +1028,13 @@ where
         token_id: String,
         msg: Binary,
     ) -> Result<Response<C>, ContractError> {
-        // Transfer token
-        self._transfer_nft(deps, &env, &info, &contract, &token_id)?;
-
         let send = Cw721ReceiveMsg {
             sender: info.sender.to_string(),
             token_id: token_id.clone(),
             msg,
         };
-
-        // Send message
-        Ok(Response::new()
-            .add_message(send.into_cosmos_msg(contract.clone())?)
-            .add_attribute("action", "send_nft")
-            .add_attribute("sender", info.sender)
-            .add_attribute("recipient", contract)
-            .add_attribute("token_id", token_id))
+        let delivery = SubMsg::new(send.into_cosmos_msg(contract.clone())?);
+        self._send_nft(deps, env, info, contract, token_id, delivery)
     }
 
     fn approve(
@@ -174,9 +1046,18 @@ where
         token_id: String,
         expires: Option<Expiration>,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+        let token =
+            self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
 
+        let event = Cw721Event::approve(
+            &info.sender,
+            token.owner,
+            &spender,
+            &token_id,
+            token.token_uri,
+        );
         Ok(Response::new()
+            .add_event(event.into())
             .add_attribute("action", "approve")
             .add_attribute("sender", info.sender)
             .add_attribute("spender", spender)
@@ -191,9 +1072,17 @@ where
         spender: String,
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+        let token = self._update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
 
+        let event = Cw721Event::revoke(
+            &info.sender,
+            token.owner,
+            &spender,
+            &token_id,
+            token.token_uri,
+        );
         Ok(Response::new()
+            .add_event(event.into())
             .add_attribute("action", "revoke")
             .add_attribute("sender", info.sender)
             .add_attribute("spender", spender)
@@ -208,6 +1097,11 @@ where
         operator: String,
         expires: Option<Expiration>,
     ) -> Result<Response<C>, ContractError> {
+        // operators could move any token, so they make no sense in a non-transferable collection
+        if self.soulbound_config(deps.storage)?.non_transferable {
+            return Err(ContractError::NonTransferable {});
+        }
+
         // reject expired data as invalid
         let expires = expires.unwrap_or_default();
         if expires.is_expired(&env.block) {
@@ -218,8 +1112,11 @@ where
         let operator_addr = deps.api.addr_validate(&operator)?;
         self.operators
             .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+        self.operator_grants
+            .save(deps.storage, (&operator_addr, &info.sender), &expires)?;
 
         Ok(Response::new()
+            .add_event(Cw721Event::approve_all(&info.sender, &operator).into())
             .add_attribute("action", "approve_all")
             .add_attribute("sender", info.sender)
             .add_attribute("operator", operator))
@@ -235,8 +1132,11 @@ where
         let operator_addr = deps.api.addr_validate(&operator)?;
         self.operators
             .remove(deps.storage, (&info.sender, &operator_addr));
+        self.operator_grants
+            .remove(deps.storage, (&operator_addr, &info.sender));
 
         Ok(Response::new()
+            .add_event(Cw721Event::revoke_all(&info.sender, &operator).into())
             .add_attribute("action", "revoke_all")
             .add_attribute("sender", info.sender)
             .add_attribute("operator", operator))
@@ -250,14 +1150,49 @@ where
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
         let token = self.tokens.load(deps.storage, &token_id)?;
-        self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        self.check_unlocked(&env, &token_id, &token)?;
+        if self.is_transferable(deps.storage, &token)? {
+            self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        } else {
+            self.check_can_revoke(deps.as_ref(), &info, &token)?;
+        }
+        self.check_transfer_hooks(deps.as_ref(), Some(&token.owner), None, &token_id)?;
 
         self.tokens.remove(deps.storage, &token_id)?;
+        self.burned_mint_seqs
+            .save(deps.storage, &token_id, &token.mint_seq)?;
+        self.clear_approvals(deps.storage, &token_id, &token.approvals);
+        self.locked_tokens
+            .remove(deps.storage, (&token.owner, &token_id));
         self.decrement_tokens(deps.storage)?;
+        self.update_balances(deps.storage, Some(&token.owner), None)?;
+        self.snapshot_owner(
+            deps.storage,
+            env.block.height,
+            &token_id,
+            Some(&token.owner),
+            None,
+        )?;
+        #[cfg(feature = "provenance")]
+        self.provenance.record(
+            deps.storage,
+            &env.block,
+            &token_id,
+            &token.owner,
+            ProvenanceAction::Burn,
+        )?;
 
+        let event = Cw721Event::burn(&info.sender, &token.owner, &token_id, token.token_uri);
+        let burned = Cw721HookMsg::Burned {
+            owner: token.owner.to_string(),
+            token_id: token_id.clone(),
+        };
         Ok(Response::new()
+            .add_submessages(self.subscriber_msgs(deps.storage, burned)?)
+            .add_event(event.into())
             .add_attribute("action", "burn")
             .add_attribute("sender", info.sender)
+            .add_attribute("owner", token.owner)
             .add_attribute("token_id", token_id))
     }
 }
@@ -268,6 +1203,136 @@ where
     T: Serialize + DeserializeOwned + Clone,
     C: CustomMsg,
 {
+    /// mints without checking who is allowed to
+    pub fn _mint(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        msg: MintMsg<T>,
+    ) -> Result<Response<C>, ContractError> {
+        if let Some(max_supply) = self.max_supply.may_load(deps.storage)? {
+            if self.mint_count(deps.storage)? >= max_supply {
+                return Err(ContractError::MaxSupplyReached { max_supply });
+            }
+        }
+
+        // reject expired locks as invalid
+        if let Some(lock_until) = msg.lock_until {
+            if lock_until.is_expired(&env.block) {
+                return Err(ContractError::Expired {});
+            }
+        }
+
+        let token_id = match msg.token_id {
+            Some(token_id) => {
+                self.check_token_id(deps.storage, &token_id)?;
+                self.reserve_token_id(deps.storage, &token_id)?;
+                token_id
+            }
+            None => {
+                let token_id = self.assign_token_id(deps.storage)?;
+                self.check_token_id(deps.storage, &token_id)?;
+                token_id
+            }
+        };
+        let owner = deps.api.addr_validate(&msg.owner)?;
+        self.check_transfer_hooks(deps.as_ref(), None, Some(&owner), &token_id)?;
+        self.update_balances(deps.storage, None, Some(&owner))?;
+        self.snapshot_owner(
+            deps.storage,
+            env.block.height,
+            &token_id,
+            None,
+            Some(&owner),
+        )?;
+        #[cfg(feature = "provenance")]
+        self.provenance.record(
+            deps.storage,
+            &env.block,
+            &token_id,
+            &owner,
+            ProvenanceAction::Mint,
+        )?;
+        if let Some(lock_until) = msg.lock_until {
+            self.locked_tokens
+                .save(deps.storage, (&owner, &token_id), &lock_until)?;
+        }
+
+        // create the token
+        let token = TokenInfo {
+            owner,
+            approvals: vec![],
+            token_uri: msg.token_uri.clone(),
+            extension: msg.extension,
+            non_transferable: msg.non_transferable.unwrap_or(false),
+            lock_until: msg.lock_until,
+            mint_seq: self.increment_mint_count(deps.storage)?,
+        };
+        self.tokens
+            .update(deps.storage, &token_id, |old| match old {
+                Some(_) => Err(ContractError::Claimed {}),
+                None => Ok(token),
+            })?;
+        self.burned_mint_seqs.remove(deps.storage, &token_id);
+
+        self.increment_tokens(deps.storage)?;
+
+        let event = Cw721Event::mint(&info.sender, &msg.owner, &token_id, msg.token_uri);
+        let minted = Cw721HookMsg::Minted {
+            owner: msg.owner.clone(),
+            token_id: token_id.clone(),
+        };
+        Ok(Response::new()
+            .add_submessages(self.subscriber_msgs(deps.storage, minted)?)
+            .add_event(event.into())
+            .add_attribute("action", "mint")
+            .add_attribute("minter", &info.sender)
+            .add_attribute("owner", msg.owner)
+            .add_attribute("token_id", &token_id)
+            .set_data(to_binary(&MintResponse { token_id })?))
+    }
+
+    /// transfers the token to the receiving contract, which is notified by `delivery`
+    pub fn _send_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        contract: String,
+        token_id: String,
+        delivery: SubMsg<C>,
+    ) -> Result<Response<C>, ContractError> {
+        // Transfer token
+        let from = self.tokens.load(deps.storage, &token_id)?.owner;
+        let token = self._transfer_nft(deps.branch(), &env, &info, &contract, &token_id)?;
+        #[cfg(feature = "provenance")]
+        self.provenance.record(
+            deps.storage,
+            &env.block,
+            &token_id,
+            &token.owner,
+            ProvenanceAction::Send,
+        )?;
+        let event = Cw721Event::send(&info.sender, &from, &contract, &token_id, token.token_uri);
+
+        let transferred = Cw721HookMsg::Transferred {
+            from: from.into_string(),
+            to: contract.clone(),
+            token_id: token_id.clone(),
+        };
+
+        // Send message
+        Ok(Response::new()
+            .add_submessage(delivery)
+            .add_submessages(self.subscriber_msgs(deps.storage, transferred)?)
+            .add_event(event.into())
+            .add_attribute("action", "send_nft")
+            .add_attribute("sender", info.sender)
+            .add_attribute("recipient", contract)
+            .add_attribute("token_id", token_id))
+    }
+
     pub fn _transfer_nft(
         &self,
         deps: DepsMut,
@@ -277,10 +1342,32 @@ where
         token_id: &str,
     ) -> Result<TokenInfo<T>, ContractError> {
         let mut token = self.tokens.load(deps.storage, token_id)?;
+        if !self.is_transferable(deps.storage, &token)? {
+            return Err(ContractError::NonTransferable {});
+        }
+        self.check_unlocked(env, token_id, &token)?;
         // ensure we have permissions
         self.check_can_send(deps.as_ref(), env, info, &token)?;
-        // set owner and remove existing approvals
-        token.owner = deps.api.addr_validate(recipient)?;
+        let recipient = deps.api.addr_validate(recipient)?;
+        self.check_transfer_hooks(
+            deps.as_ref(),
+            Some(&token.owner),
+            Some(&recipient),
+            token_id,
+        )?;
+        self.update_balances(deps.storage, Some(&token.owner), Some(&recipient))?;
+        self.snapshot_owner(
+            deps.storage,
+            env.block.height,
+            token_id,
+            Some(&token.owner),
+            Some(&recipient),
+        )?;
+        // set owner and remove existing approvals, and the expired lock
+        self.clear_approvals(deps.storage, token_id, &token.approvals);
+        self.locked_tokens
+            .remove(deps.storage, (&token.owner, token_id));
+        token.owner = recipient;
         token.approvals = vec![];
         self.tokens.save(deps.storage, token_id, &token)?;
         Ok(token)
@@ -302,21 +1389,30 @@ where
         // ensure we have permissions
         self.check_can_approve(deps.as_ref(), env, info, &token)?;
 
-        // update the approval list (remove any for the same spender before adding)
+        // update the approval list (remove any for the same spender before adding),
+        // dropping the expired ones on the way
         let spender_addr = deps.api.addr_validate(spender)?;
-        token.approvals = token
-            .approvals
-            .into_iter()
-            .filter(|apr| apr.spender != spender_addr)
-            .collect();
+        self.drop_expired_approvals(deps.storage, env, token_id, &mut token);
+        token.approvals.retain(|apr| apr.spender != spender_addr);
+        self.spender_approvals
+            .remove(deps.storage, (&spender_addr, token_id));
 
         // only difference between approve and revoke
         if add {
+            if !self.is_transferable(deps.storage, &token)? {
+                return Err(ContractError::NonTransferable {});
+            }
             // reject expired data as invalid
             let expires = expires.unwrap_or_default();
             if expires.is_expired(&env.block) {
                 return Err(ContractError::Expired {});
             }
+            let max = self.approval_limit(deps.storage)?;
+            if token.approvals.len() >= max as usize {
+                return Err(ContractError::TooManyApprovals { max });
+            }
+            self.spender_approvals
+                .save(deps.storage, (&spender_addr, token_id), &expires)?;
             let approval = Approval {
                 spender: spender_addr,
                 expires,
@@ -329,6 +1425,65 @@ where
         Ok(token)
     }
 
+    /// rejects token ids that do not follow the rules of the collection, if it has any
+    pub fn check_token_id(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        // collections without rules accept any token id, as they always did
+        let rules = match self.token_id_rules.may_load(storage)? {
+            Some(rules) => rules,
+            None => return Ok(()),
+        };
+        let invalid = |reason: String| Err(ContractError::InvalidTokenId { reason });
+
+        if token_id.is_empty() {
+            return invalid("empty".to_string());
+        }
+        let max_length = rules.max_length.unwrap_or(DEFAULT_MAX_TOKEN_ID_LENGTH);
+        if token_id.len() > max_length as usize {
+            return invalid(format!("longer than {} bytes", max_length));
+        }
+        if token_id
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+        {
+            return invalid("contains whitespace or control characters".to_string());
+        }
+        if rules.numeric_only.unwrap_or(false)
+            && (!token_id.chars().all(|c| c.is_ascii_digit())
+                || (token_id.len() > 1 && token_id.starts_with('0')))
+        {
+            return invalid("not a canonical number".to_string());
+        }
+        if let Some(ranges) = rules.char_ranges()? {
+            let allowed = |c: char| ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi);
+            if let Some(c) = token_id.chars().find(|&c| !allowed(c)) {
+                return invalid(format!("character '{}' is not allowed", c));
+            }
+        }
+        Ok(())
+    }
+
+    /// removes the expired approvals from the token and the spender index,
+    /// returns how many were removed. The caller saves the token
+    pub fn drop_expired_approvals(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        token_id: &str,
+        token: &mut TokenInfo<T>,
+    ) -> usize {
+        let (expired, active) = token
+            .approvals
+            .drain(..)
+            .partition::<Vec<_>, _>(|apr| apr.is_expired(&env.block));
+        self.clear_approvals(storage, token_id, &expired);
+        token.approvals = active;
+        expired.len()
+    }
+
     /// returns true iff the sender can execute approve or reject on the contract
     pub fn check_can_approve(
         &self,
@@ -357,7 +1512,9 @@ where
         }
     }
 
-    /// returns true iff the sender can transfer ownership of the token
+    /// returns true iff the sender can transfer ownership of the token.
+    /// Unlike approving, this does not prune expired grants: a send relying on one fails, which
+    /// would revert the removal, and a successful send clears all the token's approvals anyway
     pub fn check_can_send(
         &self,
         deps: Deps,
@@ -394,4 +1551,176 @@ where
             None => Err(ContractError::Unauthorized {}),
         }
     }
+
+    /// returns an error if the token is still locked with its owner
+    pub fn check_unlocked(
+        &self,
+        env: &Env,
+        token_id: &str,
+        token: &TokenInfo<T>,
+    ) -> Result<(), ContractError> {
+        match token.active_lock(&env.block) {
+            Some(lock_until) => Err(ContractError::Locked {
+                token_id: token_id.to_string(),
+                lock_until,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// queries every registered transfer hook, any of them can reject the token changing hands.
+    /// from is unset on mint, to is unset on burn
+    pub fn check_transfer_hooks(
+        &self,
+        deps: Deps,
+        from: Option<&Addr>,
+        to: Option<&Addr>,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        let hooks = self
+            .transfer_hooks
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for hook in hooks {
+            let check = Cw721TransferCheck {
+                from: from.map(Addr::to_string),
+                to: to.map(Addr::to_string),
+                token_id: token_id.to_string(),
+            };
+            let res: CheckTransferResponse = deps
+                .querier
+                .query(&check.into_query_request(hook.clone())?)?;
+            if !res.allowed {
+                return Err(ContractError::TransferRejected {
+                    hook: hook.into_string(),
+                    reason: res.reason.unwrap_or_default(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// builds the notification of every registered subscriber. Fail-open ones are dispatched
+    /// with a reply on error, so their failure does not revert the operation
+    pub fn subscriber_msgs(
+        &self,
+        storage: &dyn Storage,
+        msg: Cw721HookMsg,
+    ) -> StdResult<Vec<SubMsg<C>>> {
+        self.subscribers
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (subscriber, config) = item?;
+                let notify = msg.clone().into_cosmos_msg(subscriber)?;
+                Ok(if config.fail_open {
+                    SubMsg::reply_on_error(notify, SUBSCRIBER_REPLY_ID)
+                } else {
+                    SubMsg::new(notify)
+                })
+            })
+            .collect()
+    }
+
+    /// fails unless the sender has an unexpired minter role with quota left,
+    /// and takes one token off the quota
+    pub fn use_minter_role(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        let mut role = self
+            .minters
+            .may_load(storage, sender)?
+            .ok_or(ContractError::Unauthorized {})?;
+        if let Some(expires) = role.expires {
+            if expires.is_expired(&env.block) {
+                return Err(ContractError::Unauthorized {});
+            }
+        }
+        if let Some(quota) = role.quota {
+            if quota == 0 {
+                return Err(ContractError::QuotaExhausted {
+                    minter: sender.to_string(),
+                });
+            }
+            role.quota = Some(quota - 1);
+            self.minters.save(storage, sender, &role)?;
+        }
+        Ok(())
+    }
+
+    /// returns true iff the sender is the contract admin:
+    /// only the governance contract, if set, or else the admin can pass
+    pub fn check_admin(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        let admin = match self.governance.may_load(deps.storage)? {
+            Some(governance) => Some(governance),
+            None => self.admin.may_load(deps.storage)?,
+        };
+        match admin {
+            Some(admin) if admin == info.sender => Ok(()),
+            _ => Err(ContractError::Unauthorized {}),
+        }
+    }
+
+    /// only the governance contract, if set, or else the minter can pass.
+    /// Additional minter roles are checked by `use_minter_role`
+    pub fn check_minter(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        let minter = match self.governance.may_load(deps.storage)? {
+            Some(governance) => governance,
+            None => self.minter.load(deps.storage)?,
+        };
+        if minter == info.sender {
+            Ok(())
+        } else {
+            Err(ContractError::Unauthorized {})
+        }
+    }
+
+    /// returns true iff the sender can burn a non-transferable token:
+    /// only its owner, or the minter if the collection is revocable
+    pub fn check_can_revoke(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        token: &TokenInfo<T>,
+    ) -> Result<(), ContractError> {
+        if token.owner == info.sender {
+            return Ok(());
+        }
+        if self.soulbound_config(deps.storage)?.revocable && self.check_minter(deps, info).is_ok() {
+            return Ok(());
+        }
+        Err(ContractError::Unauthorized {})
+    }
+}
+
+/// derives the account address of a compressed secp256k1 public key,
+/// ie. the bech32 encoding of ripemd160(sha256(pubkey)) with the given prefix
+pub(crate) fn signer_address(pubkey: &[u8], prefix: &str) -> Result<String, ContractError> {
+    if pubkey.len() != 33 {
+        return Err(ContractError::InvalidPermit {
+            reason: "pubkey must be compressed".to_string(),
+        });
+    }
+    let hash = Ripemd160::digest(&Sha256::digest(pubkey));
+    bech32::encode(prefix, hash.to_base32(), Variant::Bech32).map_err(|err| {
+        ContractError::InvalidPermit {
+            reason: err.to_string(),
+        }
+    })
+}
+
+/// returns the human readable part of a bech32 address, e.g. `cosmos` for `cosmos1...`
+fn bech32_prefix(addr: &str) -> &str {
+    addr.rsplit_once('1').map_or("", |(prefix, _)| prefix)
+}
+
+/// makes sure the governance contract is a cw3 contract, by asking for its voting threshold
+fn validate_governance(deps: Deps, governance: &str) -> StdResult<Addr> {
+    let governance = deps.api.addr_validate(governance)?;
+    deps.querier
+        .query_wasm_smart::<ThresholdResponse>(&governance, &Cw3QueryMsg::Threshold {})
+        .map_err(|_| StdError::generic_err(format!("{} is not a cw3 contract", governance)))?;
+    Ok(governance)
 }
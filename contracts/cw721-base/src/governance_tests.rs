@@ -0,0 +1,269 @@
+#![cfg(all(test, not(feature = "library")))]
+use cosmwasm_std::{Addr, Binary, Empty, StdError};
+use cw3::{Cw3Contract, Vote};
+use cw3_fixed_multisig::msg::{InstantiateMsg as MultisigInstantiateMsg, Voter};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_utils::{Duration, Threshold};
+
+use crate::helpers::Cw721Contract;
+use crate::{
+    ContractError, ExecuteMsg, Extension, GovernanceResponse, InstantiateMsg, MintMsg,
+    MintersResponse, Operation, PauseStatusResponse, QueryMsg, VoucherSignerResponse,
+};
+
+const MINTER: &str = "merlin";
+const ADMIN: &str = "arthur";
+
+fn cw721_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::entry::execute,
+        crate::entry::instantiate,
+        crate::entry::query,
+    );
+    Box::new(contract)
+}
+
+fn multisig_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw3_fixed_multisig::contract::execute,
+        cw3_fixed_multisig::contract::instantiate,
+        cw3_fixed_multisig::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// a 2 out of 3 multisig of alice, bob and carol
+fn instantiate_multisig(app: &mut App) -> Addr {
+    let code_id = app.store_code(multisig_contract());
+    let voters = ["alice", "bob", "carol"]
+        .iter()
+        .map(|addr| Voter {
+            addr: addr.to_string(),
+            weight: 1,
+        })
+        .collect();
+    let msg = MultisigInstantiateMsg {
+        voters,
+        threshold: Threshold::AbsoluteCount { weight: 2 },
+        max_voting_period: Duration::Time(3600),
+    };
+    app.instantiate_contract(code_id, Addr::unchecked(ADMIN), &msg, &[], "multisig", None)
+        .unwrap()
+}
+
+fn instantiate_msg(governance: Option<String>) -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Council".to_string(),
+        symbol: "CNCL".to_string(),
+        minter: String::from(MINTER),
+        admin: Some(String::from(ADMIN)),
+        non_transferable: None,
+        revocable: None,
+        snapshots: None,
+        max_approvals: None,
+        token_id_rules: None,
+        max_supply: None,
+        governance,
+    }
+}
+
+fn mint_msg(token_id: &str) -> ExecuteMsg<Extension> {
+    ExecuteMsg::Mint(MintMsg {
+        token_id: Some(token_id.to_string()),
+        owner: "alice".to_string(),
+        token_uri: None,
+        extension: None,
+        non_transferable: None,
+        lock_until: None,
+    })
+}
+
+#[test]
+fn governance_must_be_cw3() {
+    let mut app = App::default();
+    let code_id = app.store_code(cw721_contract());
+
+    let err = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &instantiate_msg(Some(ADMIN.to_string())),
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<StdError>().unwrap(),
+        StdError::generic_err("arthur is not a cw3 contract")
+    );
+}
+
+#[test]
+fn admin_actions_through_proposals() {
+    let mut app = App::default();
+    let multisig = instantiate_multisig(&mut app);
+    let code_id = app.store_code(cw721_contract());
+    let collection = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &instantiate_msg(Some(multisig.to_string())),
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap();
+    let cw721 = Cw721Contract(collection.clone());
+    let cw3 = Cw3Contract(multisig.clone());
+
+    let res: GovernanceResponse = app
+        .wrap()
+        .query_wasm_smart(&collection, &QueryMsg::Governance {})
+        .unwrap();
+    assert_eq!(res.governance, Some(multisig.to_string()));
+
+    // neither the minter nor the admin can act on their own anymore
+    let err = app
+        .execute_contract(
+            Addr::unchecked(MINTER),
+            collection.clone(),
+            &mint_msg("1"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let pause_msg = ExecuteMsg::<Extension>::Pause {
+        operations: Some(vec![Operation::Burn]),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked(ADMIN), collection.clone(), &pause_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+
+    // alice proposes, which counts as their vote, and bob makes it pass
+    let proposal = cw721
+        .propose(
+            &multisig,
+            "Genesis",
+            "Mint the first token and freeze burning",
+            vec![mint_msg("1"), pause_msg],
+        )
+        .unwrap();
+    app.execute(Addr::unchecked("alice"), proposal).unwrap();
+    app.execute(Addr::unchecked("bob"), cw3.vote(1, Vote::Yes).unwrap())
+        .unwrap();
+    app.execute(Addr::unchecked("carol"), cw3.execute(1).unwrap())
+        .unwrap();
+
+    let count = cw721.num_tokens(&app.wrap()).unwrap();
+    assert_eq!(count, 1);
+    let res: PauseStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&collection, &QueryMsg::PauseStatus {})
+        .unwrap();
+    assert!(res.burn && !res.mint);
+
+    // handing control back takes a proposal too
+    let proposal = cw721
+        .propose::<Extension>(
+            &multisig,
+            "Dissolve",
+            "Give control back to the admin",
+            vec![ExecuteMsg::UpdateGovernance { governance: None }],
+        )
+        .unwrap();
+    app.execute(Addr::unchecked("bob"), proposal).unwrap();
+    app.execute(Addr::unchecked("carol"), cw3.vote(2, Vote::Yes).unwrap())
+        .unwrap();
+    app.execute(Addr::unchecked("bob"), cw3.execute(2).unwrap())
+        .unwrap();
+    app.execute_contract(Addr::unchecked(MINTER), collection, &mint_msg("2"), &[])
+        .unwrap();
+    assert_eq!(cw721.num_tokens(&app.wrap()).unwrap(), 2);
+}
+
+#[test]
+fn governance_drops_existing_minters() {
+    let mut app = App::default();
+    let multisig = instantiate_multisig(&mut app);
+    let code_id = app.store_code(cw721_contract());
+    let collection = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(ADMIN),
+            &instantiate_msg(None),
+            &[],
+            "collection",
+            None,
+        )
+        .unwrap();
+
+    // before governance, a sale contract can mint and vouchers are signed off-chain
+    let add_minter = ExecuteMsg::<Extension>::AddMinter {
+        minter: "sale".to_string(),
+        quota: None,
+        expires: None,
+    };
+    app.execute_contract(Addr::unchecked(ADMIN), collection.clone(), &add_minter, &[])
+        .unwrap();
+    let set_signer = ExecuteMsg::<Extension>::SetVoucherSigner {
+        pubkey: Some(Binary::from([2; 33].to_vec())),
+    };
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        collection.clone(),
+        &set_signer,
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("sale"),
+        collection.clone(),
+        &mint_msg("1"),
+        &[],
+    )
+    .unwrap();
+
+    // handing over to governance revokes both
+    let update = ExecuteMsg::<Extension>::UpdateGovernance {
+        governance: Some(multisig.to_string()),
+    };
+    app.execute_contract(Addr::unchecked(ADMIN), collection.clone(), &update, &[])
+        .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked("sale"),
+            collection.clone(),
+            &mint_msg("2"),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let res: MintersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &QueryMsg::Minters {
+                start_after: None,
+                limit: None,
+                page: None,
+            },
+        )
+        .unwrap();
+    assert!(res.minters.is_empty());
+    let res: VoucherSignerResponse = app
+        .wrap()
+        .query_wasm_smart(&collection, &QueryMsg::VoucherSigner {})
+        .unwrap();
+    assert_eq!(res.pubkey, None);
+}
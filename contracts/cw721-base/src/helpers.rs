@@ -1,8 +1,10 @@
 use crate::{ExecuteMsg, QueryMsg};
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, QuerierWrapper, StdResult, WasmMsg, WasmQuery};
+use cw3::Cw3Contract;
 use cw721::{
-    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse,
-    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, BalanceResponse,
+    ContractInfoResponse, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
+    TokensResponse,
 };
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -26,6 +28,22 @@ impl Cw721Contract {
         .into())
     }
 
+    /// encodes the given messages to this contract as a proposal to the cw3 contract
+    /// governing it, to be sent by one of its voters
+    pub fn propose<T: Serialize>(
+        &self,
+        governance: &Addr,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        msgs: Vec<ExecuteMsg<T>>,
+    ) -> StdResult<CosmosMsg> {
+        let msgs = msgs
+            .into_iter()
+            .map(|msg| self.call(msg))
+            .collect::<StdResult<_>>()?;
+        Cw3Contract(governance.clone()).proposal(title, description, msgs, None, None)
+    }
+
     pub fn query<T: DeserializeOwned>(
         &self,
         querier: &QuerierWrapper,
@@ -110,6 +128,14 @@ impl Cw721Contract {
         Ok(res.count)
     }
 
+    pub fn balance<T: Into<String>>(&self, querier: &QuerierWrapper, owner: T) -> StdResult<u64> {
+        let req = QueryMsg::Balance {
+            owner: owner.into(),
+        };
+        let res: BalanceResponse = self.query(querier, req)?;
+        Ok(res.balance)
+    }
+
     /// With metadata extension
     pub fn contract_info(&self, querier: &QuerierWrapper) -> StdResult<ContractInfoResponse> {
         let req = QueryMsg::ContractInfo {};
@@ -156,6 +182,7 @@ impl Cw721Contract {
             start_after,
             limit,
             page,
+            order: None,
         };
         self.query(querier, req)
     }
@@ -172,6 +199,7 @@ impl Cw721Contract {
             start_after,
             limit,
             page,
+            order: None,
         };
         self.query(querier, req)
     }
@@ -1,13 +1,27 @@
 mod contract_tests;
 mod error;
 mod execute;
+mod governance_tests;
 pub mod helpers;
 pub mod msg;
+#[cfg(feature = "provenance")]
+pub mod provenance;
 mod query;
 pub mod state;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
+pub use crate::msg::{
+    AdminResponse, AllOwnersResponse, ApprovalsForOperatorResponse, BalanceAtHeightResponse,
+    ExecuteMsg, GovernanceResponse, InstantiateMsg, LockersResponse, LocksResponse, MigrateMsg,
+    MintMsg, MintOrder, MintResponse, MinterResponse, MinterRole, MintersResponse,
+    NumOwnersResponse, Operation, OperatorGrant, OwnerBalance, OwnerOfAtHeightResponse,
+    PauseStatusResponse, Permit, PermitNonceResponse, QueryMsg, SoulboundResponse, Subscriber,
+    SubscribersResponse, SupplyInfoResponse, TokenIdRules, TokenIdRulesResponse, TokenLock,
+    TokensAtHeightResponse, TransferHooksResponse, TransferableResponse, Voucher,
+    VoucherSignerResponse,
+};
+#[cfg(feature = "provenance")]
+pub use crate::msg::{ProvenanceAction, ProvenanceRecord, TokenHistoryResponse};
 pub use crate::state::Cw721Contract;
 use cosmwasm_std::Empty;
 
@@ -19,7 +33,7 @@ pub mod entry {
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
     // This makes a conscious choice on the various generics used by the contract
     #[entry_point]
@@ -44,6 +58,18 @@ pub mod entry {
         tract.execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.migrate(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.reply(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         let tract = Cw721Contract::<Extension, Empty>::default();
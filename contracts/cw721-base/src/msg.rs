@@ -1,7 +1,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Binary;
+#[cfg(feature = "provenance")]
+use cosmwasm_std::Timestamp;
+use cosmwasm_std::{to_vec, Binary, Coin, StdError, StdResult};
 use cw721::Expiration;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -15,8 +17,87 @@ pub struct InstantiateMsg {
     /// This is designed for a base NFT that is controlled by an external program
     /// or contract. You will likely replace this with custom logic in custom NFTs
     pub minter: String,
+    /// The admin can pause and unpause the contract in an emergency.
+    /// It is separate from the minter and if unset, nobody can pause the contract
+    pub admin: Option<String>,
+
+    /// If set, no token in this collection can be transferred, sent or approved.
+    /// Owners can still burn their tokens
+    pub non_transferable: Option<bool>,
+    /// If set, the minter can revoke (burn) non-transferable tokens from their owners
+    pub revocable: Option<bool>,
+
+    /// If set, ownership is checkpointed at every block so it can be queried at past heights,
+    /// eg. for governance or airdrops. This costs extra gas on every mint, transfer and burn
+    pub snapshots: Option<bool>,
+
+    /// Maximum number of approvals a single token can have, 10 if unset
+    pub max_approvals: Option<u32>,
+
+    /// Constraints on the token_id of minted tokens, any token_id is accepted if unset
+    pub token_id_rules: Option<TokenIdRules>,
+
+    /// Maximum number of tokens that can ever be minted, burned ones included.
+    /// It cannot be changed later, unlimited if unset
+    pub max_supply: Option<u64>,
+
+    /// cw3 contract, e.g. a multisig, whose passed proposals are the only way to run
+    /// admin and minter actions if set. See `ExecuteMsg::UpdateGovernance`
+    pub governance: Option<String>,
+}
+
+/// How long a token_id can be unless set otherwise in `TokenIdRules`
+pub const DEFAULT_MAX_TOKEN_ID_LENGTH: u32 = 256;
+
+/// Constraints on the token_id of minted tokens. Once set, empty IDs and IDs with whitespace
+/// or control characters are rejected too
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TokenIdRules {
+    /// Maximum length in bytes, 256 if unset
+    pub max_length: Option<u32>,
+    /// Allowed characters, written like a regex character class without the brackets,
+    /// e.g. "a-z0-9_-". Any character is allowed if unset
+    pub charset: Option<String>,
+    /// If set, only canonical decimal numbers are allowed, e.g. "42" but not "042"
+    pub numeric_only: Option<bool>,
+}
+
+impl TokenIdRules {
+    /// parses the charset into inclusive character ranges
+    pub fn char_ranges(&self) -> StdResult<Option<Vec<(char, char)>>> {
+        let charset = match &self.charset {
+            Some(charset) if charset.is_empty() => {
+                return Err(StdError::generic_err("Token id charset is empty"))
+            }
+            Some(charset) => charset.chars().collect::<Vec<_>>(),
+            None => return Ok(None),
+        };
+        let mut ranges = vec![];
+        let mut i = 0;
+        while i < charset.len() {
+            // a dash at either end is taken literally
+            if i + 2 < charset.len() && charset[i + 1] == '-' {
+                let (lo, hi) = (charset[i], charset[i + 2]);
+                if lo > hi {
+                    return Err(StdError::generic_err(format!(
+                        "Invalid token id charset range {}-{}",
+                        lo, hi
+                    )));
+                }
+                ranges.push((lo, hi));
+                i += 3;
+            } else {
+                ranges.push((charset[i], charset[i]));
+                i += 1;
+            }
+        }
+        Ok(Some(ranges))
+    }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 /// This is like Cw721ExecuteMsg but we add a Mint command for an owner
 /// to make this stand-alone. You will likely want to remove mint and
 /// use other control logic in any contract that inherits this.
@@ -32,6 +113,14 @@ pub enum ExecuteMsg<T> {
         token_id: String,
         msg: Binary,
     },
+    /// Like `SendNft`, but fails upfront if `contract` is not a contract, and reverts
+    /// the transfer with a `ReceiverFailed` error if the receiver rejects the token.
+    /// Any funds sent along are forwarded to the receiver
+    SafeSendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
@@ -39,6 +128,14 @@ pub enum ExecuteMsg<T> {
         token_id: String,
         expires: Option<Expiration>,
     },
+    /// Approves like `Approve` on behalf of the owner or an operator who signed the permit,
+    /// so they do not need to send a transaction themselves. `pubkey` is the signer's
+    /// compressed secp256k1 public key, and `signature` signs `Permit::sign_doc`
+    ApproveWithPermit {
+        permit: Permit,
+        pubkey: Binary,
+        signature: Binary,
+    },
     /// Remove previously granted Approval
     Revoke { spender: String, token_id: String },
     /// Allows operator to transfer / send any token from the owner's account.
@@ -49,18 +146,139 @@ pub enum ExecuteMsg<T> {
     },
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
+    /// Remove all approvals of a token at once
+    RevokeAllApprovals { token_id: String },
+    /// Read up to `limit` operators of the owner after `start_after` and remove the expired ones.
+    /// The `next_start_after` attribute is set if there may be more. Can be called by anyone
+    PruneExpired {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Remove the expired approvals of the given tokens, 30 at most. Can be called by anyone
+    PruneTokenApprovals { token_ids: Vec<String> },
 
     /// Mint a new NFT, can only be called by the contract minter
     Mint(MintMsg<T>),
+    /// Mint the token described by a voucher the minter signed off-chain, to the sender.
+    /// The sender must pay the voucher's price, which goes to the minter.
+    /// `signature` signs `Voucher::sign_doc`
+    MintWithVoucher {
+        voucher: Voucher<T>,
+        signature: Binary,
+    },
+    /// Register the compressed public key that signs vouchers, or stop accepting them if unset.
+    /// Can only be called by the contract minter
+    SetVoucherSigner { pubkey: Option<Binary> },
 
     /// Burn an NFT the sender has access to
     Burn { token_id: String },
+
+    /// Hand over the admin role to another address, or drop it altogether.
+    /// Can only be called by the current admin
+    UpdateAdmin { admin: Option<String> },
+    /// Hand over admin and minter actions to a cw3 contract, or give them back to the
+    /// admin and minter if unset. Setting it removes all minter roles and the voucher signer.
+    /// Can only be called by the admin, ie. by the current governance contract once set
+    UpdateGovernance { governance: Option<String> },
+    /// Halt the given operations, or all of them if unset. Can only be called by the admin
+    Pause { operations: Option<Vec<Operation>> },
+    /// Resume the given operations, or all of them if unset. Can only be called by the admin
+    Unpause { operations: Option<Vec<Operation>> },
+    /// Change how many approvals a single token can have. Tokens that already have more
+    /// keep them, but cannot get new ones until enough are revoked or expired.
+    /// Can only be called by the admin
+    SetMaxApprovals { max_approvals: u32 },
+
+    /// Allow the address to mint besides the minter, up to `quota` tokens and until `expires`
+    /// if set. Replaces the previous role of that address. Can only be called by the admin
+    AddMinter {
+        minter: String,
+        quota: Option<u64>,
+        expires: Option<Expiration>,
+    },
+    /// Remove a previously added minter. Can only be called by the admin
+    RemoveMinter { minter: String },
+
+    /// Allow the address to lock tokens. Can only be called by the admin
+    AddLocker { locker: String },
+    /// Remove a previously added locker. Can only be called by the admin
+    RemoveLocker { locker: String },
+    /// Keep the token with its current owner until `lock_until`. An active lock can only be
+    /// extended. Can only be called by a locker
+    LockToken {
+        token_id: String,
+        lock_until: Expiration,
+    },
+
+    /// Register a contract to be queried before every mint, transfer, send and burn.
+    /// It can veto the operation. Can only be called by the admin
+    AddTransferHook { hook: String },
+    /// Remove a previously registered transfer hook. Can only be called by the admin
+    RemoveTransferHook { hook: String },
+
+    /// Register a contract to be notified with a `Cw721HookMsg` after every mint, transfer,
+    /// send and burn. If `fail_open` is set, a failing subscriber is ignored, otherwise it
+    /// reverts the whole operation. Can only be called by the admin
+    AddSubscriber {
+        subscriber: String,
+        fail_open: Option<bool>,
+    },
+    /// Remove a previously registered subscriber. Can only be called by the admin
+    RemoveSubscriber { subscriber: String },
+
+    /// Keep at most this many provenance entries per token, dropping the oldest ones.
+    /// Can only be called by the admin
+    #[cfg(feature = "provenance")]
+    SetHistoryRetention { max_entries: u32 },
+}
+
+impl<T> ExecuteMsg<T> {
+    /// The class of operation this message belongs to, if it can be paused.
+    /// Revoking approvals and admin messages are always allowed
+    pub fn operation(&self) -> Option<Operation> {
+        match self {
+            ExecuteMsg::Mint(_) | ExecuteMsg::MintWithVoucher { .. } => Some(Operation::Mint),
+            ExecuteMsg::TransferNft { .. }
+            | ExecuteMsg::SendNft { .. }
+            | ExecuteMsg::SafeSendNft { .. } => Some(Operation::Transfer),
+            ExecuteMsg::Approve { .. }
+            | ExecuteMsg::ApproveWithPermit { .. }
+            | ExecuteMsg::ApproveAll { .. } => Some(Operation::Approve),
+            ExecuteMsg::Burn { .. } => Some(Operation::Burn),
+            _ => None,
+        }
+    }
+}
+
+/// The classes of state-changing operations that can be paused independently
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Mint,
+    /// `TransferNft`, `SendNft` and `SafeSendNft`
+    Transfer,
+    /// `Approve`, `ApproveWithPermit` and `ApproveAll`
+    Approve,
+    Burn,
+}
+
+impl Operation {
+    pub fn all() -> Vec<Operation> {
+        vec![
+            Operation::Mint,
+            Operation::Transfer,
+            Operation::Approve,
+            Operation::Burn,
+        ]
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MintMsg<T> {
-    /// Unique ID of the NFT
-    pub token_id: String,
+    /// Unique ID of the NFT. If unset, the next free sequential ID is assigned,
+    /// and returned in the response data as `MintResponse`
+    pub token_id: Option<String>,
     /// The owner of the newly minter NFT
     pub owner: String,
     /// Universal resource identifier for this NFT
@@ -69,6 +287,71 @@ pub struct MintMsg<T> {
     pub token_uri: Option<String>,
     /// Any custom extension used by this contract
     pub extension: T,
+    /// Mint this token as non-transferable, even if the rest of the collection is not
+    pub non_transferable: Option<bool>,
+    /// Keep this token locked with its owner (no transfer, send or burn) until then
+    pub lock_until: Option<Expiration>,
+}
+
+/// A mint signed off-chain by the voucher signer, see `ExecuteMsg::MintWithVoucher`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Voucher<T> {
+    /// A voucher can only be redeemed once, even if the token is burned later
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub extension: T,
+    /// What the sender has to pay, free if unset
+    pub price: Option<Coin>,
+    pub expires: Option<Expiration>,
+    /// Chain and contract the voucher is valid for, so it cannot be replayed elsewhere
+    pub chain_id: String,
+    pub contract: String,
+}
+
+impl<T: Serialize> Voucher<T> {
+    /// returns the ADR-036 sign doc of the voucher, signed by the voucher signer's account,
+    /// see `sign_doc`
+    pub fn sign_doc(&self, signer: &str) -> StdResult<Vec<u8>> {
+        Ok(sign_doc(signer, &to_vec(self)?))
+    }
+}
+
+/// An approval signed off-chain, see `ExecuteMsg::ApproveWithPermit`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permit {
+    pub spender: String,
+    pub token_id: String,
+    /// Expiration of the approval. An expired permit cannot be used anymore, so a leaked one
+    /// stays usable only until then. Must not be `never`
+    pub expires: Expiration,
+    /// Must be the signer's current `PermitNonce`, so a permit can only be used once
+    pub nonce: u64,
+    /// Chain and contract the permit is valid for, so it cannot be replayed elsewhere
+    pub chain_id: String,
+    pub contract: String,
+}
+
+impl Permit {
+    /// returns the ADR-036 sign doc of the permit, signed by the owner's or operator's account,
+    /// see `sign_doc`
+    pub fn sign_doc(&self, signer: &str) -> StdResult<Vec<u8>> {
+        Ok(sign_doc(signer, &to_vec(self)?))
+    }
+}
+
+/// Returns the ADR-036 sign doc that Cosmos wallets sign for `signArbitrary(chain_id, signer, data)`,
+/// so permits and vouchers can be signed with Keplr, Leap or cosmjs. `data` is the compact JSON of the
+/// permit or voucher, with the fields in the order they are declared and unset options as `null`, e.g.
+/// `{"spender":"bella","token_id":"grow","expires":{"at_height":12445},"nonce":0,"chain_id":"cosmos-testnet-14002","contract":"cosmos1..."}`.
+/// The signature is the 64 byte secp256k1 signature of the sha256 hash of these bytes:
+/// `{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"<base64 of data>","signer":"<signer>"}}],"sequence":"0"}`
+pub fn sign_doc(signer: &str, data: &[u8]) -> Vec<u8> {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        Binary::from(data).to_base64(),
+        signer
+    )
+    .into_bytes()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -107,8 +390,43 @@ pub enum QueryMsg {
         limit: Option<u32>,
         page: Option<u32>,
     },
+    /// List all owners that made the given address an operator of all their tokens
+    /// Return type: `ApprovalsForOperatorResponse`
+    ApprovalsForOperator {
+        operator: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+    /// List all tokens the given address was approved to transfer or send
+    /// Return type: `TokensResponse`
+    TokensApprovedFor {
+        spender: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
     /// Total number of tokens issued
     NumTokens {},
+    /// Number of tokens held by the given address.
+    /// Return type: `BalanceResponse`
+    Balance {
+        owner: String,
+    },
+    /// Lists every distinct owner with the number of tokens they hold.
+    /// Return type: `AllOwnersResponse`
+    AllOwners {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+    /// Number of distinct owners.
+    /// Return type: `NumOwnersResponse`
+    NumOwners {},
 
     /// With MetaData Extension.
     /// Returns top-level metadata about the contract: `ContractInfoResponse`
@@ -136,6 +454,8 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
         page: Option<u32>,
+        /// List in mint order instead of by token_id
+        order: Option<MintOrder>,
     },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
@@ -144,10 +464,115 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
         page: Option<u32>,
+        /// List in mint order instead of by token_id
+        order: Option<MintOrder>,
     },
 
     // Return the minter
     Minter {},
+    /// Lists the addresses allowed to mint besides the minter, with their remaining quota.
+    /// Return type: `MintersResponse`
+    Minters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+
+    /// Returns whether the given token can currently change hands.
+    /// Return type: `TransferableResponse`
+    Transferable {
+        token_id: String,
+    },
+    /// Returns the collection-wide non-transferable settings.
+    /// Return type: `SoulboundResponse`
+    Soulbound {},
+
+    /// The public key vouchers must be signed with, if any.
+    /// Return type: `VoucherSignerResponse`
+    VoucherSigner {},
+    /// The nonce the next permit signed by the given address must use.
+    /// Return type: `PermitNonceResponse`
+    PermitNonce {
+        signer: String,
+    },
+
+    /// Maximum, minted, burned and circulating number of tokens.
+    /// Return type: `SupplyInfoResponse`
+    SupplyInfo {},
+    /// Constraints minted token ids must follow.
+    /// Return type: `TokenIdRulesResponse`
+    TokenIdRules {},
+
+    /// Return the admin, if any
+    /// Return type: `AdminResponse`
+    Admin {},
+    /// Return the cw3 governance contract, if any
+    /// Return type: `GovernanceResponse`
+    Governance {},
+    /// Returns which operations are currently paused.
+    /// Return type: `PauseStatusResponse`
+    PauseStatus {},
+
+    /// Lists the tokens of the given owner that are still locked, reading at most `limit` locks.
+    /// Return type: `LocksResponse`
+    Locks {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+    /// Lists all addresses allowed to lock tokens.
+    /// Return type: `LockersResponse`
+    Lockers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+
+    /// Lists all contracts queried before a token changes hands.
+    /// Return type: `TransferHooksResponse`
+    TransferHooks {},
+
+    /// Lists all contracts notified after a token was minted, transferred or burned.
+    /// Return type: `SubscribersResponse`
+    Subscribers {},
+
+    /// Return the owner of the given token at the start of the given block height,
+    /// unset if it did not exist then. Requires snapshot mode.
+    /// Return type: `OwnerOfAtHeightResponse`
+    OwnerOfAtHeight {
+        token_id: String,
+        height: u64,
+    },
+    /// Returns the tokens held by the given address at the start of the given block height.
+    /// Reads `limit` of the tokens the address ever held at most, so a page can hold fewer,
+    /// continue from `last_scanned`. Requires snapshot mode.
+    /// Return type: `TokensAtHeightResponse`
+    TokensAtHeight {
+        owner: String,
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
+    /// Returns how many tokens the given address held at the start of the given block height.
+    /// Requires snapshot mode.
+    /// Return type: `BalanceAtHeightResponse`
+    BalanceAtHeight {
+        owner: String,
+        height: u64,
+    },
+
+    /// Lists the ownership history of the token, oldest first.
+    /// Return type: `TokenHistoryResponse`
+    #[cfg(feature = "provenance")]
+    TokenHistory {
+        token_id: String,
+        /// Index of the last entry of the previous page
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    },
 }
 
 /// Shows who can mint these tokens
@@ -155,3 +580,201 @@ pub enum QueryMsg {
 pub struct MinterResponse {
     pub minter: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TransferableResponse {
+    pub transferable: bool,
+}
+
+/// Shows who can pause the contract
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AdminResponse {
+    pub admin: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PauseStatusResponse {
+    pub mint: bool,
+    pub transfer: bool,
+    pub approve: bool,
+    pub burn: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenLock {
+    pub token_id: String,
+    pub lock_until: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LocksResponse {
+    pub locks: Vec<TokenLock>,
+    /// The `start_after` of the next page, unset once every lock of the owner was read
+    pub last_scanned: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LockersResponse {
+    pub lockers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TransferHooksResponse {
+    pub hooks: Vec<String>,
+}
+
+/// What changed the ownership of a token
+#[cfg(feature = "provenance")]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceAction {
+    Mint,
+    Transfer,
+    Send,
+    Burn,
+}
+
+#[cfg(feature = "provenance")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ProvenanceRecord {
+    /// Position in the token history, used for pagination
+    pub index: u64,
+    /// Owner of the token after the action, or the last owner on burn
+    pub owner: String,
+    pub height: u64,
+    pub time: Timestamp,
+    pub action: ProvenanceAction,
+}
+
+#[cfg(feature = "provenance")]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenHistoryResponse {
+    pub history: Vec<ProvenanceRecord>,
+}
+
+/// Direction to list tokens in mint order, `start_after` must then be a minted token, burned or not
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MintOrder {
+    /// Oldest first
+    Ascending,
+    /// Newest first
+    Descending,
+}
+
+/// Data of the response to `ExecuteMsg::Mint`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintResponse {
+    pub token_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OperatorGrant {
+    /// Owner that made the operator an operator
+    pub granter: String,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ApprovalsForOperatorResponse {
+    pub grants: Vec<OperatorGrant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OwnerBalance {
+    pub owner: String,
+    pub balance: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinterRole {
+    pub address: String,
+    /// How many more tokens it can mint, unlimited if unset
+    pub quota: Option<u64>,
+    pub expires: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintersResponse {
+    pub minters: Vec<MinterRole>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GovernanceResponse {
+    pub governance: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllOwnersResponse {
+    pub owners: Vec<OwnerBalance>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NumOwnersResponse {
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct OwnerOfAtHeightResponse {
+    pub owner: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokensAtHeightResponse {
+    pub tokens: Vec<String>,
+    /// The `start_after` of the next page, unset once every token the address held was read
+    pub last_scanned: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BalanceAtHeightResponse {
+    pub balance: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Subscriber {
+    pub address: String,
+    /// If set, failures of this subscriber are ignored
+    pub fail_open: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SubscribersResponse {
+    pub subscribers: Vec<Subscriber>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SupplyInfoResponse {
+    /// Unset if unlimited
+    pub max_supply: Option<u64>,
+    /// Number of tokens ever minted
+    pub minted: u64,
+    pub burned: u64,
+    /// Number of tokens in existence, ie. minted but not burned
+    pub circulating: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VoucherSignerResponse {
+    pub pubkey: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenIdRulesResponse {
+    /// Unset if the collection has no rules, and accepts any token_id
+    pub max_length: Option<u32>,
+    pub charset: Option<String>,
+    pub numeric_only: bool,
+}
+
+/// Shows whether tokens of this collection can change hands
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct SoulboundResponse {
+    pub non_transferable: bool,
+    pub revocable: bool,
+}
@@ -0,0 +1,153 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    Addr, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+    Timestamp,
+};
+
+use cw721::CustomMsg;
+use cw_storage_plus::{Bound, Item, Map};
+
+use crate::error::ContractError;
+use crate::msg::{ProvenanceAction, ProvenanceRecord, TokenHistoryResponse};
+use crate::state::Cw721Contract;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_PAGE: u32 = 0;
+
+/// How many entries are kept per token unless the admin sets another cap
+pub const DEFAULT_HISTORY_RETENTION: u32 = 100;
+
+/// Append-only ownership log of every token, oldest entries are dropped past the retention cap
+pub struct ProvenanceLog<'a> {
+    /// Stored as (token_id, sequence number)
+    pub entries: Map<'a, (&'a str, u64), ProvenanceEntry>,
+    pub bounds: Map<'a, &'a str, HistoryBounds>,
+    pub retention: Item<'a, u32>,
+}
+
+impl<'a> ProvenanceLog<'a> {
+    pub fn new(entries_key: &'a str, bounds_key: &'a str, retention_key: &'a str) -> Self {
+        Self {
+            entries: Map::new(entries_key),
+            bounds: Map::new(bounds_key),
+            retention: Item::new(retention_key),
+        }
+    }
+
+    pub fn retention(&self, storage: &dyn Storage) -> StdResult<u32> {
+        Ok(self
+            .retention
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_HISTORY_RETENTION))
+    }
+
+    /// appends an entry to the token history and drops the oldest ones beyond the retention cap
+    pub fn record(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+        owner: &Addr,
+        action: ProvenanceAction,
+    ) -> StdResult<()> {
+        let mut bounds = self.bounds.may_load(storage, token_id)?.unwrap_or_default();
+        let entry = ProvenanceEntry {
+            owner: owner.clone(),
+            height: block.height,
+            time: block.time,
+            action,
+        };
+        self.entries
+            .save(storage, (token_id, bounds.next), &entry)?;
+        bounds.next += 1;
+
+        let retention = self.retention(storage)? as u64;
+        while bounds.next - bounds.first > retention {
+            self.entries.remove(storage, (token_id, bounds.first));
+            bounds.first += 1;
+        }
+        self.bounds.save(storage, token_id, &bounds)
+    }
+}
+
+impl Default for ProvenanceLog<'static> {
+    fn default() -> Self {
+        Self::new("history", "history_bounds", "history_retention")
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProvenanceEntry {
+    /// Owner of the token after the action, or the last owner on burn
+    pub owner: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+    pub action: ProvenanceAction,
+}
+
+/// Sequence numbers of the oldest kept entry and of the next one of a token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct HistoryBounds {
+    pub first: u64,
+    pub next: u64,
+}
+
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn set_history_retention(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        max_entries: u32,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        self.provenance.retention.save(deps.storage, &max_entries)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_history_retention")
+            .add_attribute("sender", info.sender)
+            .add_attribute("max_entries", max_entries.to_string()))
+    }
+
+    pub fn token_history(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<TokenHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start = start_after.map(Bound::exclusive);
+
+        let history = self
+            .provenance
+            .entries
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .map(|item| {
+                item.map(|(index, entry)| ProvenanceRecord {
+                    index,
+                    owner: entry.owner.into_string(),
+                    height: entry.height,
+                    time: entry.time,
+                    action: entry.action,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(TokenHistoryResponse { history })
+    }
+}
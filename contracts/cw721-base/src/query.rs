@@ -4,14 +4,22 @@ use serde::Serialize;
 use cosmwasm_std::{to_binary, Addr, Binary, BlockInfo, Deps, Env, Order, StdError, StdResult};
 
 use cw721::{
-    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, CustomMsg,
-    Cw721Query, Expiration, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
-    TokensResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, BalanceResponse, ContractInfoResponse,
+    CustomMsg, Cw721Query, Expiration, NftInfoResponse, NumTokensResponse, OperatorsResponse,
+    OwnerOfResponse, TokensResponse,
 };
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
-use crate::msg::{MinterResponse, QueryMsg};
+use crate::msg::{
+    AdminResponse, AllOwnersResponse, ApprovalsForOperatorResponse, BalanceAtHeightResponse,
+    GovernanceResponse, LockersResponse, LocksResponse, MintOrder, MinterResponse, MinterRole,
+    MintersResponse, NumOwnersResponse, OperatorGrant, OwnerBalance, OwnerOfAtHeightResponse,
+    PauseStatusResponse, PermitNonceResponse, QueryMsg, SoulboundResponse, Subscriber,
+    SubscribersResponse, SupplyInfoResponse, TokenIdRulesResponse, TokenLock,
+    TokensAtHeightResponse, TransferHooksResponse, TransferableResponse, VoucherSignerResponse,
+    DEFAULT_MAX_TOKEN_ID_LENGTH,
+};
 use crate::state::{Approval, Cw721Contract, TokenInfo};
 
 const DEFAULT_LIMIT: u32 = 10;
@@ -32,6 +40,12 @@ where
         Ok(NumTokensResponse { count })
     }
 
+    fn balance(&self, deps: Deps, owner: String) -> StdResult<BalanceResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let balance = self.balance_of(deps.storage, &owner_addr)?;
+        Ok(BalanceResponse { balance })
+    }
+
     fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
         let info = self.tokens.load(deps.storage, &token_id)?;
         Ok(NftInfoResponse {
@@ -228,9 +242,486 @@ where
         })
     }
 
+    pub fn minters(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<MintersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let minters = self
+            .minters
+            .range(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .map(|item| {
+                item.map(|(address, role)| MinterRole {
+                    address: address.into_string(),
+                    quota: role.quota,
+                    expires: role.expires,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(MintersResponse { minters })
+    }
+
+    pub fn transferable(&self, deps: Deps, token_id: String) -> StdResult<TransferableResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        let transferable = self.is_transferable(deps.storage, &token)?;
+        Ok(TransferableResponse { transferable })
+    }
+
+    pub fn soulbound(&self, deps: Deps) -> StdResult<SoulboundResponse> {
+        let config = self.soulbound_config(deps.storage)?;
+        Ok(SoulboundResponse {
+            non_transferable: config.non_transferable,
+            revocable: config.revocable,
+        })
+    }
+
+    pub fn voucher_signer(&self, deps: Deps) -> StdResult<VoucherSignerResponse> {
+        let pubkey = self.voucher_signer.may_load(deps.storage)?;
+        Ok(VoucherSignerResponse { pubkey })
+    }
+
+    pub fn permit_nonce(&self, deps: Deps, signer: String) -> StdResult<PermitNonceResponse> {
+        let signer = deps.api.addr_validate(&signer)?;
+        let nonce = self.next_permit_nonce(deps.storage, &signer)?;
+        Ok(PermitNonceResponse { nonce })
+    }
+
+    pub fn supply_info(&self, deps: Deps) -> StdResult<SupplyInfoResponse> {
+        let minted = self.mint_count(deps.storage)?;
+        let circulating = self.token_count(deps.storage)?;
+        Ok(SupplyInfoResponse {
+            max_supply: self.max_supply.may_load(deps.storage)?,
+            minted,
+            burned: minted - circulating,
+            circulating,
+        })
+    }
+
+    pub fn token_id_rules(&self, deps: Deps) -> StdResult<TokenIdRulesResponse> {
+        let res = match self.token_id_rules.may_load(deps.storage)? {
+            Some(rules) => TokenIdRulesResponse {
+                max_length: Some(rules.max_length.unwrap_or(DEFAULT_MAX_TOKEN_ID_LENGTH)),
+                charset: rules.charset,
+                numeric_only: rules.numeric_only.unwrap_or(false),
+            },
+            None => TokenIdRulesResponse {
+                max_length: None,
+                charset: None,
+                numeric_only: false,
+            },
+        };
+        Ok(res)
+    }
+
+    pub fn admin(&self, deps: Deps) -> StdResult<AdminResponse> {
+        let admin = self.admin.may_load(deps.storage)?;
+        Ok(AdminResponse {
+            admin: admin.map(Addr::into_string),
+        })
+    }
+
+    pub fn governance(&self, deps: Deps) -> StdResult<GovernanceResponse> {
+        let governance = self.governance.may_load(deps.storage)?;
+        Ok(GovernanceResponse {
+            governance: governance.map(Addr::into_string),
+        })
+    }
+
+    pub fn pause_status(&self, deps: Deps) -> StdResult<PauseStatusResponse> {
+        let state = self.pause_state(deps.storage)?;
+        Ok(PauseStatusResponse {
+            mint: state.mint,
+            transfer: state.transfer,
+            approve: state.approve,
+            burn: state.burn,
+        })
+    }
+
+    pub fn locks(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<LocksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        // expired locks stay stored until the token moves, so at most `limit` of them are read
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let scanned = self
+            .locked_tokens
+            .prefix(&owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        let last_scanned = if scanned.len() == limit {
+            scanned.last().map(|(token_id, _)| token_id.clone())
+        } else {
+            None
+        };
+
+        let locks = scanned
+            .into_iter()
+            .filter(|(_, lock_until)| !lock_until.is_expired(&env.block))
+            .map(|(token_id, lock_until)| TokenLock {
+                token_id,
+                lock_until,
+            })
+            .collect();
+
+        Ok(LocksResponse {
+            locks,
+            last_scanned,
+        })
+    }
+
+    pub fn lockers(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<LockersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let lockers = self
+            .lockers
+            .keys(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .map(|x| x.map(Addr::into_string))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(LockersResponse { lockers })
+    }
+
+    pub fn transfer_hooks(&self, deps: Deps) -> StdResult<TransferHooksResponse> {
+        let hooks = self
+            .transfer_hooks
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|x| x.map(Addr::into_string))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(TransferHooksResponse { hooks })
+    }
+
+    pub fn subscribers(&self, deps: Deps) -> StdResult<SubscribersResponse> {
+        let subscribers = self
+            .subscribers
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(address, config)| Subscriber {
+                    address: address.into_string(),
+                    fail_open: config.fail_open,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(SubscribersResponse { subscribers })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn approvals_for_operator(
+        &self,
+        deps: Deps,
+        env: Env,
+        operator: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<ApprovalsForOperatorResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        let grants = self
+            .operator_grants
+            .prefix(&operator_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|r| {
+                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
+            })
+            .skip(page)
+            .take(limit)
+            .map(|item| {
+                item.map(|(granter, expires)| OperatorGrant {
+                    granter: granter.into_string(),
+                    expires,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(ApprovalsForOperatorResponse { grants })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn tokens_approved_for(
+        &self,
+        deps: Deps,
+        env: Env,
+        spender: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        let tokens = self
+            .spender_approvals
+            .prefix(&spender_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|r| {
+                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
+            })
+            .skip(page)
+            .take(limit)
+            .map(|item| item.map(|(token_id, _)| token_id))
+            .collect::<StdResult<_>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    /// lists the tokens of the owner, or all tokens if unset, in mint order
+    pub fn tokens_in_mint_order(
+        &self,
+        deps: Deps,
+        owner: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+        order: MintOrder,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        // the cursor is the position of the given token in mint order, which outlives a burn
+        let cursor = start_after
+            .map(|token_id| {
+                let mint_seq = match self.tokens.may_load(deps.storage, &token_id)? {
+                    Some(token) => token.mint_seq,
+                    None => self.burned_mint_seqs.load(deps.storage, &token_id)?,
+                };
+                StdResult::Ok(Bound::exclusive((mint_seq, token_id)))
+            })
+            .transpose()?;
+        let (min, max, order) = match order {
+            MintOrder::Ascending => (cursor, None, Order::Ascending),
+            MintOrder::Descending => (None, cursor, Order::Descending),
+        };
+
+        let tokens = match owner {
+            Some(owner) => {
+                let owner_addr = deps.api.addr_validate(&owner)?;
+                self.tokens
+                    .idx
+                    .owner_mint_order
+                    .sub_prefix(owner_addr)
+                    .keys(deps.storage, min, max, order)
+            }
+            None => self
+                .tokens
+                .idx
+                .mint_order
+                .keys(deps.storage, min, max, order),
+        }
+        .skip(page)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    pub fn all_owners(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<AllOwnersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let owners = self
+            .balances
+            .range(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .map(|item| {
+                item.map(|(owner, balance)| OwnerBalance {
+                    owner: owner.into_string(),
+                    balance,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(AllOwnersResponse { owners })
+    }
+
+    pub fn num_owners(&self, deps: Deps) -> StdResult<NumOwnersResponse> {
+        let count = self.owner_count(deps.storage)?;
+        Ok(NumOwnersResponse { count })
+    }
+
+    pub fn owner_of_at_height(
+        &self,
+        deps: Deps,
+        token_id: String,
+        height: u64,
+    ) -> StdResult<OwnerOfAtHeightResponse> {
+        self.check_snapshots(deps)?;
+        let owner = self
+            .owner_snapshots
+            .may_load_at_height(deps.storage, &token_id, height)?;
+        Ok(OwnerOfAtHeightResponse {
+            owner: owner.map(Addr::into_string),
+        })
+    }
+
+    pub fn tokens_at_height(
+        &self,
+        deps: Deps,
+        owner: String,
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        page: Option<u32>,
+    ) -> StdResult<TokensAtHeightResponse> {
+        self.check_snapshots(deps)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let page = page.unwrap_or(DEFAULT_PAGE) as usize * limit;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        // only tokens the address ever held are candidates, and at most `limit` of them are read,
+        // as that history only grows
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let candidates = self
+            .past_owners
+            .prefix(&owner_addr)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .skip(page)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        let last_scanned = if candidates.len() == limit {
+            candidates.last().cloned()
+        } else {
+            None
+        };
+
+        let mut tokens = vec![];
+        for token_id in candidates {
+            let owner = self
+                .owner_snapshots
+                .may_load_at_height(deps.storage, &token_id, height)?;
+            if owner.as_ref() == Some(&owner_addr) {
+                tokens.push(token_id);
+            }
+        }
+
+        Ok(TokensAtHeightResponse {
+            tokens,
+            last_scanned,
+        })
+    }
+
+    pub fn balance_at_height(
+        &self,
+        deps: Deps,
+        owner: String,
+        height: u64,
+    ) -> StdResult<BalanceAtHeightResponse> {
+        self.check_snapshots(deps)?;
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let balance = self
+            .balance_snapshots
+            .may_load_at_height(deps.storage, &owner_addr, height)?
+            .unwrap_or_default();
+        Ok(BalanceAtHeightResponse { balance })
+    }
+
+    fn check_snapshots(&self, deps: Deps) -> StdResult<()> {
+        if self.snapshots_enabled(deps.storage)? {
+            Ok(())
+        } else {
+            Err(StdError::generic_err("Snapshot mode is not enabled"))
+        }
+    }
+
     pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
             QueryMsg::Minter {} => to_binary(&self.minter(deps)?),
+            QueryMsg::Minters {
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.minters(deps, start_after, limit, page)?),
+            QueryMsg::Transferable { token_id } => to_binary(&self.transferable(deps, token_id)?),
+            QueryMsg::Soulbound {} => to_binary(&self.soulbound(deps)?),
+            QueryMsg::VoucherSigner {} => to_binary(&self.voucher_signer(deps)?),
+            QueryMsg::PermitNonce { signer } => to_binary(&self.permit_nonce(deps, signer)?),
+            QueryMsg::SupplyInfo {} => to_binary(&self.supply_info(deps)?),
+            QueryMsg::TokenIdRules {} => to_binary(&self.token_id_rules(deps)?),
+            QueryMsg::Admin {} => to_binary(&self.admin(deps)?),
+            QueryMsg::Governance {} => to_binary(&self.governance(deps)?),
+            QueryMsg::PauseStatus {} => to_binary(&self.pause_status(deps)?),
+            QueryMsg::Locks {
+                owner,
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.locks(deps, env, owner, start_after, limit, page)?),
+            QueryMsg::Lockers {
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.lockers(deps, start_after, limit, page)?),
+            QueryMsg::TransferHooks {} => to_binary(&self.transfer_hooks(deps)?),
+            QueryMsg::Subscribers {} => to_binary(&self.subscribers(deps)?),
+            QueryMsg::OwnerOfAtHeight { token_id, height } => {
+                to_binary(&self.owner_of_at_height(deps, token_id, height)?)
+            }
+            QueryMsg::TokensAtHeight {
+                owner,
+                height,
+                start_after,
+                limit,
+                page,
+            } => {
+                to_binary(&self.tokens_at_height(deps, owner, height, start_after, limit, page)?)
+            }
+            QueryMsg::BalanceAtHeight { owner, height } => {
+                to_binary(&self.balance_at_height(deps, owner, height)?)
+            }
+            #[cfg(feature = "provenance")]
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.token_history(deps, token_id, start_after, limit, page)?),
             QueryMsg::ContractInfo {} => to_binary(&self.contract_info(deps)?),
             QueryMsg::NftInfo { token_id } => to_binary(&self.nft_info(deps, token_id)?),
             QueryMsg::OwnerOf {
@@ -263,18 +754,84 @@ where
                 limit,
                 page,
             )?),
+            QueryMsg::ApprovalsForOperator {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.approvals_for_operator(
+                deps,
+                env,
+                operator,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+                page,
+            )?),
+            QueryMsg::TokensApprovedFor {
+                spender,
+                include_expired,
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.tokens_approved_for(
+                deps,
+                env,
+                spender,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+                page,
+            )?),
             QueryMsg::NumTokens {} => to_binary(&self.num_tokens(deps)?),
+            QueryMsg::Balance { owner } => to_binary(&self.balance(deps, owner)?),
+            QueryMsg::AllOwners {
+                start_after,
+                limit,
+                page,
+            } => to_binary(&self.all_owners(deps, start_after, limit, page)?),
+            QueryMsg::NumOwners {} => to_binary(&self.num_owners(deps)?),
             QueryMsg::Tokens {
                 owner,
                 start_after,
                 limit,
                 page,
+                order: None,
             } => to_binary(&self.tokens(deps, owner, start_after, limit, page)?),
+            QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+                page,
+                order: Some(order),
+            } => to_binary(&self.tokens_in_mint_order(
+                deps,
+                Some(owner),
+                start_after,
+                limit,
+                page,
+                order,
+            )?),
             QueryMsg::AllTokens {
                 start_after,
                 limit,
                 page,
+                order: None,
             } => to_binary(&self.all_tokens(deps, start_after, limit, page)?),
+            QueryMsg::AllTokens {
+                start_after,
+                limit,
+                page,
+                order: Some(order),
+            } => to_binary(&self.tokens_in_mint_order(
+                deps,
+                None,
+                start_after,
+                limit,
+                page,
+                order,
+            )?),
             QueryMsg::Approval {
                 token_id,
                 spender,
@@ -3,10 +3,18 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
-use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Empty, StdError, StdResult, Storage};
 
 use cw721::{ContractInfoResponse, CustomMsg, Cw721, Expiration};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+
+use crate::error::ContractError;
+use crate::msg::{Operation, TokenIdRules};
+#[cfg(feature = "provenance")]
+use crate::provenance::ProvenanceLog;
+
+/// How many approvals a token can have unless set otherwise at instantiation
+pub const DEFAULT_MAX_APPROVALS: u32 = 10;
 
 pub struct Cw721Contract<'a, T, C>
 where
@@ -14,10 +22,63 @@ where
 {
     pub contract_info: Item<'a, ContractInfoResponse>,
     pub minter: Item<'a, Addr>,
+    /// Addresses allowed to mint besides the minter
+    pub minters: Map<'a, &'a Addr, MinterConfig>,
     pub token_count: Item<'a, u64>,
+    /// Next candidate for sequential token IDs, never decremented
+    pub next_token_id: Item<'a, u64>,
+    /// Number of tokens ever minted, which is also the mint sequence number of the next one
+    pub mint_count: Item<'a, u64>,
+    /// Mint sequence number of burned tokens, so they still work as a cursor in mint order
+    pub burned_mint_seqs: Map<'a, &'a str, u64>,
+    /// Set at instantiation, unlimited if unset
+    pub max_supply: Item<'a, u64>,
+    /// Public key of the minter's vouchers, unset if vouchers are not accepted
+    pub voucher_signer: Item<'a, Binary>,
+    /// Token IDs of the vouchers already used
+    pub redeemed_vouchers: Map<'a, &'a str, Empty>,
     /// Stored as (granter, operator) giving operator full control over granter's account
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
+    /// Reverse of operators, stored as (operator, granter)
+    pub operator_grants: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
+    /// Per-token approvals, stored as (spender, token_id)
+    pub spender_approvals: Map<'a, (&'a Addr, &'a str), Expiration>,
+    /// Nonce of the next permit of every signer
+    pub permit_nonces: Map<'a, &'a Addr, u64>,
+    /// Maximum number of approvals a single token can have
+    pub max_approvals: Item<'a, u32>,
+    /// Number of tokens of every owner, owners without tokens are removed
+    pub balances: Map<'a, &'a Addr, u64>,
+    /// Number of entries in balances, ie. of distinct owners
+    pub num_owners: Item<'a, u64>,
+    pub soulbound: Item<'a, SoulboundConfig>,
+    pub token_id_rules: Item<'a, TokenIdRules>,
+    /// Can pause and unpause the contract, unset if nobody can
+    pub admin: Item<'a, Addr>,
+    /// cw3 contract standing in for both the admin and the minter, if set
+    pub governance: Item<'a, Addr>,
+    pub pause: Item<'a, PauseState>,
+    /// Addresses allowed to lock tokens
+    pub lockers: Map<'a, &'a Addr, Empty>,
+    /// Locks of every owner's tokens, stored as (owner, token_id) until the token changes hands
+    pub locked_tokens: Map<'a, (&'a Addr, &'a str), Expiration>,
+    /// Contracts that can veto any token changing hands
+    pub transfer_hooks: Map<'a, &'a Addr, Empty>,
+    /// Contracts notified after a token was minted, transferred or burned
+    pub subscribers: Map<'a, &'a Addr, SubscriberConfig>,
+    /// Safe sends waiting for the receiver's reply, the innermost one last
+    pub pending_sends: Item<'a, Vec<PendingSend>>,
+    #[cfg(feature = "provenance")]
+    pub provenance: ProvenanceLog<'a>,
+    /// Set at instantiation, enables the `*AtHeight` queries
+    pub snapshots: Item<'a, bool>,
+    /// Owner of every token, checkpointed at every block in snapshot mode
+    pub owner_snapshots: SnapshotMap<'a, &'a str, Addr>,
+    /// Number of tokens of every owner, checkpointed at every block in snapshot mode
+    pub balance_snapshots: SnapshotMap<'a, &'a Addr, u64>,
+    /// Every (owner, token_id) pair that ever existed in snapshot mode
+    pub past_owners: Map<'a, (&'a Addr, &'a str), Empty>,
 
     pub(crate) _custom_response: PhantomData<C>,
 }
@@ -38,10 +99,43 @@ where
         Self::new(
             "nft_info",
             "minter",
+            "minters",
             "num_tokens",
+            "next_token_id",
+            "mint_count",
+            "burned_mint_seqs",
+            "max_supply",
+            "voucher_signer",
+            "redeemed_vouchers",
             "operators",
+            "operator_grants",
             "tokens",
             "tokens__owner",
+            "tokens__mint_order",
+            "tokens__owner_mint_order",
+            "spender_approvals",
+            "permit_nonces",
+            "max_approvals",
+            "balances",
+            "num_owners",
+            "soulbound",
+            "token_id_rules",
+            "admin",
+            "governance",
+            "pause",
+            "lockers",
+            "locked_tokens",
+            "transfer_hooks",
+            "subscribers",
+            "pending_sends",
+            "snapshots",
+            "owner_snapshots",
+            "owner_snapshots__checkpoints",
+            "owner_snapshots__changelog",
+            "balance_snapshots",
+            "balance_snapshots__checkpoints",
+            "balance_snapshots__changelog",
+            "past_owners",
         )
     }
 }
@@ -50,23 +144,102 @@ impl<'a, T, C> Cw721Contract<'a, T, C>
 where
     T: Serialize + DeserializeOwned + Clone,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         contract_key: &'a str,
         minter_key: &'a str,
+        minters_key: &'a str,
         token_count_key: &'a str,
+        next_token_id_key: &'a str,
+        mint_count_key: &'a str,
+        burned_mint_seqs_key: &'a str,
+        max_supply_key: &'a str,
+        voucher_signer_key: &'a str,
+        redeemed_vouchers_key: &'a str,
         operator_key: &'a str,
+        operator_grants_key: &'a str,
         tokens_key: &'a str,
         tokens_owner_key: &'a str,
+        tokens_mint_order_key: &'a str,
+        tokens_owner_mint_order_key: &'a str,
+        spender_approvals_key: &'a str,
+        permit_nonces_key: &'a str,
+        max_approvals_key: &'a str,
+        balances_key: &'a str,
+        num_owners_key: &'a str,
+        soulbound_key: &'a str,
+        token_id_rules_key: &'a str,
+        admin_key: &'a str,
+        governance_key: &'a str,
+        pause_key: &'a str,
+        lockers_key: &'a str,
+        locked_tokens_key: &'a str,
+        transfer_hooks_key: &'a str,
+        subscribers_key: &'a str,
+        pending_sends_key: &'a str,
+        snapshots_key: &'a str,
+        owner_snapshots_key: &'a str,
+        owner_checkpoints_key: &'a str,
+        owner_changelog_key: &'a str,
+        balance_snapshots_key: &'a str,
+        balance_checkpoints_key: &'a str,
+        balance_changelog_key: &'a str,
+        past_owners_key: &'a str,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, tokens_key, tokens_owner_key),
+            mint_order: MultiIndex::new(token_mint_order_idx, tokens_key, tokens_mint_order_key),
+            owner_mint_order: MultiIndex::new(
+                token_owner_mint_order_idx,
+                tokens_key,
+                tokens_owner_mint_order_key,
+            ),
         };
         Self {
             contract_info: Item::new(contract_key),
             minter: Item::new(minter_key),
+            minters: Map::new(minters_key),
             token_count: Item::new(token_count_key),
+            next_token_id: Item::new(next_token_id_key),
+            mint_count: Item::new(mint_count_key),
+            burned_mint_seqs: Map::new(burned_mint_seqs_key),
+            max_supply: Item::new(max_supply_key),
+            voucher_signer: Item::new(voucher_signer_key),
+            redeemed_vouchers: Map::new(redeemed_vouchers_key),
             operators: Map::new(operator_key),
+            operator_grants: Map::new(operator_grants_key),
             tokens: IndexedMap::new(tokens_key, indexes),
+            spender_approvals: Map::new(spender_approvals_key),
+            permit_nonces: Map::new(permit_nonces_key),
+            max_approvals: Item::new(max_approvals_key),
+            balances: Map::new(balances_key),
+            num_owners: Item::new(num_owners_key),
+            soulbound: Item::new(soulbound_key),
+            token_id_rules: Item::new(token_id_rules_key),
+            admin: Item::new(admin_key),
+            governance: Item::new(governance_key),
+            pause: Item::new(pause_key),
+            lockers: Map::new(lockers_key),
+            locked_tokens: Map::new(locked_tokens_key),
+            transfer_hooks: Map::new(transfer_hooks_key),
+            subscribers: Map::new(subscribers_key),
+            pending_sends: Item::new(pending_sends_key),
+            #[cfg(feature = "provenance")]
+            provenance: ProvenanceLog::default(),
+            snapshots: Item::new(snapshots_key),
+            owner_snapshots: SnapshotMap::new(
+                owner_snapshots_key,
+                owner_checkpoints_key,
+                owner_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            balance_snapshots: SnapshotMap::new(
+                balance_snapshots_key,
+                balance_checkpoints_key,
+                balance_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            past_owners: Map::new(past_owners_key),
             _custom_response: PhantomData,
         }
     }
@@ -86,6 +259,188 @@ where
         self.token_count.save(storage, &val)?;
         Ok(val)
     }
+
+    pub fn mint_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.mint_count.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// returns the mint sequence number of a new token
+    pub fn increment_mint_count(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let seq = self.mint_count(storage)?;
+        self.mint_count.save(storage, &(seq + 1))?;
+        Ok(seq)
+    }
+
+    /// takes the next sequential token ID, skipping the ones already minted explicitly
+    pub fn assign_token_id(&self, storage: &mut dyn Storage) -> Result<String, ContractError> {
+        let mut id = self.next_token_id.may_load(storage)?.unwrap_or_default();
+        while self.tokens.key(&id.to_string()).has(storage) {
+            id = id
+                .checked_add(1)
+                .ok_or(ContractError::TokenIdsExhausted {})?;
+        }
+        let next = id
+            .checked_add(1)
+            .ok_or(ContractError::TokenIdsExhausted {})?;
+        self.next_token_id.save(storage, &next)?;
+        Ok(id.to_string())
+    }
+
+    /// moves the sequential token IDs past an explicitly minted numeric one,
+    /// so it is not assigned again once burned. The last u64 cannot be reserved,
+    /// as there would be no sequential ID left after it
+    pub fn reserve_token_id(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if let Some(id) = numeric_token_id(token_id) {
+            if id >= self.next_token_id.may_load(storage)?.unwrap_or_default() {
+                let next = id
+                    .checked_add(1)
+                    .ok_or(ContractError::TokenIdsExhausted {})?;
+                self.next_token_id.save(storage, &next)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn balance_of(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<u64> {
+        Ok(self.balances.may_load(storage, owner)?.unwrap_or_default())
+    }
+
+    /// moves one token from `from` to `to` in the balances.
+    /// from is unset on mint, to is unset on burn
+    pub fn update_balances(
+        &self,
+        storage: &mut dyn Storage,
+        from: Option<&Addr>,
+        to: Option<&Addr>,
+    ) -> StdResult<()> {
+        if let Some(from) = from {
+            match self.balance_of(storage, from)? {
+                0 | 1 => {
+                    self.balances.remove(storage, from);
+                    let owners = self.owner_count(storage)?.saturating_sub(1);
+                    self.num_owners.save(storage, &owners)?;
+                }
+                balance => self.balances.save(storage, from, &(balance - 1))?,
+            }
+        }
+        if let Some(to) = to {
+            let balance = self.balance_of(storage, to)? + 1;
+            self.balances.save(storage, to, &balance)?;
+            if balance == 1 {
+                let owners = self.owner_count(storage)? + 1;
+                self.num_owners.save(storage, &owners)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn next_permit_nonce(&self, storage: &dyn Storage, signer: &Addr) -> StdResult<u64> {
+        Ok(self
+            .permit_nonces
+            .may_load(storage, signer)?
+            .unwrap_or_default())
+    }
+
+    pub fn approval_limit(&self, storage: &dyn Storage) -> StdResult<u32> {
+        Ok(self
+            .max_approvals
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_MAX_APPROVALS))
+    }
+
+    /// drops the given approvals of a token from the spender index
+    pub fn clear_approvals(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        approvals: &[Approval],
+    ) {
+        for approval in approvals {
+            self.spender_approvals
+                .remove(storage, (&approval.spender, token_id));
+        }
+    }
+
+    pub fn owner_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.num_owners.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn soulbound_config(&self, storage: &dyn Storage) -> StdResult<SoulboundConfig> {
+        Ok(self.soulbound.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// returns false if either the collection or the token itself is non-transferable
+    pub fn is_transferable(&self, storage: &dyn Storage, token: &TokenInfo<T>) -> StdResult<bool> {
+        let config = self.soulbound_config(storage)?;
+        Ok(!config.non_transferable && !token.non_transferable)
+    }
+
+    /// remembers a safe send until the receiver's reply
+    pub fn push_pending_send(&self, storage: &mut dyn Storage, send: PendingSend) -> StdResult<()> {
+        let mut pending = self.pending_sends.may_load(storage)?.unwrap_or_default();
+        pending.push(send);
+        self.pending_sends.save(storage, &pending)
+    }
+
+    /// takes the innermost safe send, which is the one a reply belongs to
+    /// as submessages are executed depth-first
+    pub fn pop_pending_send(&self, storage: &mut dyn Storage) -> StdResult<PendingSend> {
+        let mut pending = self.pending_sends.may_load(storage)?.unwrap_or_default();
+        let send = pending
+            .pop()
+            .ok_or_else(|| StdError::generic_err("No pending safe send"))?;
+        if pending.is_empty() {
+            self.pending_sends.remove(storage);
+        } else {
+            self.pending_sends.save(storage, &pending)?;
+        }
+        Ok(send)
+    }
+
+    pub fn pause_state(&self, storage: &dyn Storage) -> StdResult<PauseState> {
+        Ok(self.pause.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn snapshots_enabled(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.snapshots.may_load(storage)?.unwrap_or(false))
+    }
+
+    /// checkpoints a token changing hands in snapshot mode.
+    /// from is unset on mint, to is unset on burn
+    pub fn snapshot_owner(
+        &self,
+        storage: &mut dyn Storage,
+        height: u64,
+        token_id: &str,
+        from: Option<&Addr>,
+        to: Option<&Addr>,
+    ) -> StdResult<()> {
+        if !self.snapshots_enabled(storage)? {
+            return Ok(());
+        }
+        if let Some(from) = from {
+            self.balance_snapshots
+                .update(storage, from, height, |balance| -> StdResult<_> {
+                    Ok(balance.unwrap_or_default().saturating_sub(1))
+                })?;
+        }
+        match to {
+            Some(to) => {
+                self.owner_snapshots.save(storage, token_id, to, height)?;
+                self.balance_snapshots
+                    .update(storage, to, height, |balance| -> StdResult<_> {
+                        Ok(balance.unwrap_or_default() + 1)
+                    })?;
+                self.past_owners.save(storage, (to, token_id), &Empty {})?;
+            }
+            None => self.owner_snapshots.remove(storage, token_id, height)?,
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -102,6 +457,81 @@ pub struct TokenInfo<T> {
 
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
+
+    /// Non-transferable tokens stay with their owner until burned
+    #[serde(default)]
+    pub non_transferable: bool,
+
+    /// Locked tokens cannot be transferred, sent or burned until this expires
+    pub lock_until: Option<Expiration>,
+
+    /// Position of the token in mint order
+    #[serde(default)]
+    pub mint_seq: u64,
+}
+
+impl<T> TokenInfo<T> {
+    /// returns the lock expiration if the token is still locked
+    pub fn active_lock(&self, block: &BlockInfo) -> Option<Expiration> {
+        self.lock_until.filter(|lock| !lock.is_expired(block))
+    }
+}
+
+/// Which classes of operations are currently halted by the admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PauseState {
+    pub mint: bool,
+    pub transfer: bool,
+    pub approve: bool,
+    pub burn: bool,
+}
+
+impl PauseState {
+    pub fn is_paused(&self, operation: Operation) -> bool {
+        match operation {
+            Operation::Mint => self.mint,
+            Operation::Transfer => self.transfer,
+            Operation::Approve => self.approve,
+            Operation::Burn => self.burn,
+        }
+    }
+
+    pub fn set(&mut self, operation: Operation, paused: bool) {
+        match operation {
+            Operation::Mint => self.mint = paused,
+            Operation::Transfer => self.transfer = paused,
+            Operation::Approve => self.approve = paused,
+            Operation::Burn => self.burn = paused,
+        }
+    }
+}
+
+/// Collection-wide settings for non-transferable (soulbound) tokens
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct SoulboundConfig {
+    /// If set, no token in the collection can be transferred, sent or approved
+    pub non_transferable: bool,
+    /// If set, the minter can burn non-transferable tokens from their owners
+    pub revocable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterConfig {
+    /// Remaining number of tokens it can mint, unlimited if unset
+    pub quota: Option<u64>,
+    pub expires: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SubscriberConfig {
+    /// If set, a failing subscriber does not revert the operation it is notified of
+    pub fail_open: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSend {
+    pub contract: Addr,
+    pub token_id: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -123,6 +553,8 @@ where
     T: Serialize + DeserializeOwned + Clone,
 {
     pub owner: MultiIndex<'a, Addr, TokenInfo<T>, Addr>,
+    pub mint_order: MultiIndex<'a, u64, TokenInfo<T>, String>,
+    pub owner_mint_order: MultiIndex<'a, (Addr, u64), TokenInfo<T>, String>,
 }
 
 impl<'a, T> IndexList<TokenInfo<T>> for TokenIndexes<'a, T>
@@ -130,7 +562,8 @@ where
     T: Serialize + DeserializeOwned + Clone,
 {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo<T>>> + '_> {
-        let v: Vec<&dyn Index<TokenInfo<T>>> = vec![&self.owner];
+        let v: Vec<&dyn Index<TokenInfo<T>>> =
+            vec![&self.owner, &self.mint_order, &self.owner_mint_order];
         Box::new(v.into_iter())
     }
 }
@@ -138,3 +571,19 @@ where
 pub fn token_owner_idx<T>(d: &TokenInfo<T>) -> Addr {
     d.owner.clone()
 }
+
+pub fn token_mint_order_idx<T>(d: &TokenInfo<T>) -> u64 {
+    d.mint_seq
+}
+
+pub fn token_owner_mint_order_idx<T>(d: &TokenInfo<T>) -> (Addr, u64) {
+    (d.owner.clone(), d.mint_seq)
+}
+
+/// returns the number of a token ID in the sequential format, ie. without sign or leading zeros
+pub(crate) fn numeric_token_id(token_id: &str) -> Option<u64> {
+    token_id
+        .parse::<u64>()
+        .ok()
+        .filter(|id| id.to_string() == token_id)
+}
@@ -59,6 +59,14 @@ pub fn instantiate(
                 name: msg.name.clone(),
                 symbol: msg.symbol,
                 minter: env.contract.address.to_string(),
+                admin: None,
+                non_transferable: None,
+                revocable: None,
+                snapshots: None,
+                max_approvals: None,
+                token_id_rules: None,
+                max_supply: Some(msg.max_tokens.into()),
+                governance: None,
             })?,
             funds: vec![],
             admin: None,
@@ -78,7 +86,7 @@ pub fn instantiate(
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
-    if config.cw721_address != None {
+    if config.cw721_address.is_some() {
         return Err(ContractError::Cw721AlreadyLinked {});
     }
 
@@ -141,7 +149,7 @@ pub fn execute_receive(
         return Err(ContractError::UnauthorizedTokenContract {});
     }
 
-    if config.cw721_address == None {
+    if config.cw721_address.is_none() {
         return Err(ContractError::Uninitialized {});
     }
 
@@ -153,11 +161,14 @@ pub fn execute_receive(
         return Err(ContractError::WrongPaymentAmount {});
     }
 
+    // the collection assigns the next token id
     let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
-        token_id: config.unused_token_id.to_string(),
+        token_id: None,
         owner: sender,
         token_uri: config.token_uri.clone().into(),
         extension: config.extension.clone(),
+        non_transferable: None,
+        lock_until: None,
     });
 
     let callback = CosmosMsg::Wasm(WasmMsg::Execute {
@@ -217,6 +228,14 @@ mod tests {
                         name: msg.name.clone(),
                         symbol: msg.symbol.clone(),
                         minter: MOCK_CONTRACT_ADDR.to_string(),
+                        admin: None,
+                        non_transferable: None,
+                        revocable: None,
+                        snapshots: None,
+                        max_approvals: None,
+                        token_id_rules: None,
+                        max_supply: Some(msg.max_tokens.into()),
+                        governance: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -362,10 +381,12 @@ mod tests {
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
         let mint_msg = Cw721ExecuteMsg::Mint(MintMsg::<Extension> {
-            token_id: String::from("0"),
+            token_id: None,
             owner: String::from("minter"),
             token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
             extension: None,
+            non_transferable: None,
+            lock_until: None,
         });
 
         assert_eq!(
@@ -4,10 +4,20 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
 use cw721::{
-    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, NftInfoResponse,
-    NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, BalanceResponse, ContractInfoResponse,
+    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+};
+use cw721_base::{
+    AdminResponse, AllOwnersResponse, ApprovalsForOperatorResponse, BalanceAtHeightResponse,
+    GovernanceResponse, LockersResponse, LocksResponse, MintResponse, MintersResponse,
+    NumOwnersResponse, OwnerOfAtHeightResponse, PauseStatusResponse, PermitNonceResponse,
+    SoulboundResponse, SubscribersResponse, SupplyInfoResponse, TokenIdRulesResponse,
+    TokensAtHeightResponse, TransferHooksResponse, TransferableResponse, VoucherSignerResponse,
+};
+use cw721_metadata_onchain::{
+    ExecuteMsg, Extension, InstantiateMsg, MigrateMsg, MinterResponse, NftMetadataJsonResponse,
+    QueryMsg,
 };
-use cw721_metadata_onchain::{ExecuteMsg, Extension, InstantiateMsg, MinterResponse, QueryMsg};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,6 +26,7 @@ fn main() {
     remove_schemas(&out_dir).unwrap();
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema_with_title(
@@ -28,12 +39,37 @@ fn main() {
     export_schema(&schema_for!(OperatorsResponse), &out_dir);
     export_schema(&schema_for!(ContractInfoResponse), &out_dir);
     export_schema(&schema_for!(MinterResponse), &out_dir);
+    export_schema(&schema_for!(MintersResponse), &out_dir);
     export_schema_with_title(
         &schema_for!(NftInfoResponse<Extension>),
         &out_dir,
         "NftInfoResponse",
     );
+    export_schema(&schema_for!(NftMetadataJsonResponse), &out_dir);
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
+    export_schema(&schema_for!(BalanceResponse), &out_dir);
+    export_schema(&schema_for!(MintResponse), &out_dir);
+    export_schema(&schema_for!(AllOwnersResponse), &out_dir);
+    export_schema(&schema_for!(ApprovalsForOperatorResponse), &out_dir);
+    export_schema(&schema_for!(NumOwnersResponse), &out_dir);
     export_schema(&schema_for!(OwnerOfResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
+    export_schema(&schema_for!(TransferableResponse), &out_dir);
+    export_schema(&schema_for!(SoulboundResponse), &out_dir);
+    export_schema(&schema_for!(TokenIdRulesResponse), &out_dir);
+    export_schema(&schema_for!(VoucherSignerResponse), &out_dir);
+    export_schema(&schema_for!(PermitNonceResponse), &out_dir);
+    export_schema(&schema_for!(SupplyInfoResponse), &out_dir);
+    export_schema(&schema_for!(AdminResponse), &out_dir);
+    export_schema(&schema_for!(GovernanceResponse), &out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), &out_dir);
+    export_schema(&schema_for!(LocksResponse), &out_dir);
+    export_schema(&schema_for!(LockersResponse), &out_dir);
+    export_schema(&schema_for!(TransferHooksResponse), &out_dir);
+    export_schema(&schema_for!(SubscribersResponse), &out_dir);
+    export_schema(&schema_for!(OwnerOfAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(TokensAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(BalanceAtHeightResponse), &out_dir);
+    #[cfg(feature = "provenance")]
+    export_schema(&schema_for!(cw721_base::TokenHistoryResponse), &out_dir);
 }
@@ -1,8 +1,13 @@
+pub mod msg;
+pub mod query;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Empty;
-pub use cw721_base::{ContractError, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
+pub use cw721_base::{ContractError, InstantiateMsg, MigrateMsg, MintMsg, MinterResponse};
+pub use msg::{MetadataQueryMsg, NftMetadataJsonResponse, QueryMsg};
+pub use query::query_nft_metadata_json;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct Trait {
@@ -35,7 +40,9 @@ pub mod entry {
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{
+        to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    };
 
     // This is a simple type to let us handle empty extensions
 
@@ -60,9 +67,24 @@ pub mod entry {
         Cw721MetadataContract::default().execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+        Cw721MetadataContract::default().migrate(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        Cw721MetadataContract::default().reply(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-        Cw721MetadataContract::default().query(deps, env, msg)
+        match msg {
+            QueryMsg::Metadata(MetadataQueryMsg::NftMetadataJson { token_id }) => {
+                to_binary(&query_nft_metadata_json(deps, token_id)?)
+            }
+            QueryMsg::Base(msg) => Cw721MetadataContract::default().query(deps, env, msg),
+        }
     }
 }
 
@@ -71,7 +93,9 @@ mod tests {
     use super::*;
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cw721::Cw721Query;
+    use cosmwasm_std::{from_binary, from_slice, Binary};
+    use cw721::{Cw721Query, NumTokensResponse};
+    use cw721_base::QueryMsg as Cw721QueryMsg;
 
     const CREATOR: &str = "creator";
 
@@ -85,6 +109,14 @@ mod tests {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
             minter: CREATOR.to_string(),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: None,
+            max_supply: None,
+            governance: None,
         };
         contract
             .instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)
@@ -92,7 +124,7 @@ mod tests {
 
         let token_id = "Enterprise";
         let mint_msg = MintMsg {
-            token_id: token_id.to_string(),
+            token_id: Some(token_id.to_string()),
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: Some(Metadata {
@@ -100,6 +132,8 @@ mod tests {
                 name: Some("Starship USS Enterprise".to_string()),
                 ..Metadata::default()
             }),
+            non_transferable: None,
+            lock_until: None,
         };
         let exec_msg = ExecuteMsg::Mint(mint_msg.clone());
         contract
@@ -110,4 +144,110 @@ mod tests {
         assert_eq!(res.token_uri, mint_msg.token_uri);
         assert_eq!(res.extension, mint_msg.extension);
     }
+
+    #[test]
+    fn render_metadata_json() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            minter: CREATOR.to_string(),
+            admin: None,
+            non_transferable: None,
+            revocable: None,
+            snapshots: None,
+            max_approvals: None,
+            token_id_rules: None,
+            max_supply: None,
+            governance: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let token_id = "Enterprise";
+        let mint_msg = MintMsg {
+            token_id: Some(token_id.to_string()),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                name: Some("Starship USS Enterprise".to_string()),
+                image_data: Some("<svg></svg>".to_string()),
+                attributes: Some(vec![Trait {
+                    display_type: None,
+                    trait_type: "class".to_string(),
+                    value: "Constitution".to_string(),
+                }]),
+                ..Metadata::default()
+            }),
+            non_transferable: None,
+            lock_until: None,
+        };
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            ExecuteMsg::Mint(mint_msg),
+        )
+        .unwrap();
+
+        let query_msg: QueryMsg =
+            from_slice(br#"{"nft_metadata_json":{"token_id":"Enterprise"}}"#).unwrap();
+        assert_eq!(
+            query_msg,
+            QueryMsg::Metadata(MetadataQueryMsg::NftMetadataJson {
+                token_id: token_id.to_string(),
+            })
+        );
+        let res: NftMetadataJsonResponse =
+            from_binary(&entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        let expected = r#"{"name":"Starship USS Enterprise","image_data":"<svg></svg>","attributes":[{"trait_type":"class","value":"Constitution"}]}"#;
+        assert_eq!(res.token_uri, None);
+        assert_eq!(res.json.as_deref(), Some(expected));
+        assert_eq!(
+            res.data_uri,
+            Some(format!(
+                "data:application/json;base64,{}",
+                Binary::from(expected.as_bytes()).to_base64()
+            ))
+        );
+
+        // tokens without on-chain metadata only point to their token_uri
+        let token_id = "Voyager";
+        let mint_msg = MintMsg {
+            token_id: Some(token_id.to_string()),
+            owner: "john".to_string(),
+            token_uri: Some("https://starships.example.com/Starship/Voyager.json".into()),
+            extension: None,
+            non_transferable: None,
+            lock_until: None,
+        };
+        entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::Mint(mint_msg.clone()),
+        )
+        .unwrap();
+
+        let res = query_nft_metadata_json(deps.as_ref(), token_id.to_string()).unwrap();
+        assert_eq!(
+            res,
+            NftMetadataJsonResponse {
+                token_uri: mint_msg.token_uri,
+                json: None,
+                data_uri: None,
+            }
+        );
+
+        // base queries are still served, in their usual format
+        let query_msg: QueryMsg = from_slice(br#"{"num_tokens":{}}"#).unwrap();
+        assert_eq!(query_msg, QueryMsg::Base(Cw721QueryMsg::NumTokens {}));
+        let res: NumTokensResponse =
+            from_binary(&entry::query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        assert_eq!(res.count, 2);
+
+        let err = from_slice::<QueryMsg>(br#"{"no_such_query":{}}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown variant `no_such_query`"));
+    }
 }
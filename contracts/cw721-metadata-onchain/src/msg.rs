@@ -0,0 +1,113 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::de::value::StringDeserializer;
+use serde::de::{self, DeserializeSeed, EnumAccess, IntoDeserializer, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use cw721_base::msg::QueryMsg as Cw721QueryMsg;
+
+/// Queries this contract adds on top of the cw721-base ones
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataQueryMsg {
+    /// Renders the on-chain metadata of the given token as an
+    /// *ERC721 Metadata JSON Schema* document.
+    /// Return type: `NftMetadataJsonResponse`
+    NftMetadataJson { token_id: String },
+}
+
+const METADATA_QUERIES: &[&str] = &["nft_metadata_json"];
+
+/// Either a `MetadataQueryMsg` or any cw721-base query, in the same externally
+/// tagged format as both, so the base queries don't need to be repeated here
+#[derive(Serialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(untagged)]
+pub enum QueryMsg {
+    Metadata(MetadataQueryMsg),
+    Base(Cw721QueryMsg),
+}
+
+impl From<MetadataQueryMsg> for QueryMsg {
+    fn from(msg: MetadataQueryMsg) -> QueryMsg {
+        QueryMsg::Metadata(msg)
+    }
+}
+
+impl From<Cw721QueryMsg> for QueryMsg {
+    fn from(msg: Cw721QueryMsg) -> QueryMsg {
+        QueryMsg::Base(msg)
+    }
+}
+
+// serde-json-wasm can't deserialize untagged enums, so read the variant name
+// first and hand the rest of the message to the enum that has it
+impl<'de> Deserialize<'de> for QueryMsg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("QueryMsg", METADATA_QUERIES, QueryMsgVisitor)
+    }
+}
+
+struct QueryMsgVisitor;
+
+impl<'de> Visitor<'de> for QueryMsgVisitor {
+    type Value = QueryMsg;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a cw721 or metadata query")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<QueryMsg, A::Error> {
+        let (name, variant) = data.variant::<String>()?;
+        let is_metadata = METADATA_QUERIES.contains(&name.as_str());
+        let tagged = TaggedVariant { name, variant };
+        if is_metadata {
+            MetadataQueryMsg::deserialize(tagged).map(QueryMsg::Metadata)
+        } else {
+            Cw721QueryMsg::deserialize(tagged).map(QueryMsg::Base)
+        }
+    }
+}
+
+/// An enum variant whose name was already read
+struct TaggedVariant<V> {
+    name: String,
+    variant: V,
+}
+
+impl<'de, A: de::VariantAccess<'de>> EnumAccess<'de> for TaggedVariant<A> {
+    type Error = A::Error;
+    type Variant = A;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, A), A::Error> {
+        let name: StringDeserializer<A::Error> = self.name.into_deserializer();
+        Ok((seed.deserialize(name)?, self.variant))
+    }
+}
+
+impl<'de, A: de::VariantAccess<'de>> Deserializer<'de> for TaggedVariant<A> {
+    type Error = A::Error;
+
+    fn deserialize_any<X: Visitor<'de>>(self, visitor: X) -> Result<X::Value, A::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NftMetadataJsonResponse {
+    /// Universal resource identifier for this NFT, as given at mint.
+    /// Clients should fall back to this when the token has no on-chain metadata
+    pub token_uri: Option<String>,
+    /// The on-chain metadata as an ERC721 Metadata JSON document, unset fields omitted.
+    /// None if the token was minted without an extension
+    pub json: Option<String>,
+    /// `json` as a `data:application/json;base64,...` URI, which lets fully on-chain
+    /// tokens (eg. `image_data` SVGs) be used wherever a metadata URI is expected
+    pub data_uri: Option<String>,
+}
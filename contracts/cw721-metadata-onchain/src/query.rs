@@ -0,0 +1,102 @@
+use serde::Serialize;
+
+use cosmwasm_std::{to_vec, Binary, Deps, StdError, StdResult};
+
+use crate::msg::NftMetadataJsonResponse;
+use crate::{Cw721MetadataContract, Metadata, Trait};
+
+const JSON_DATA_URI_PREFIX: &str = "data:application/json;base64,";
+
+/// Canonical ERC721 Metadata JSON document. Unlike `Metadata` itself, unset
+/// fields are left out rather than rendered as `null`
+#[derive(Serialize)]
+struct MetadataJson<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image_data: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attributes: Option<Vec<TraitJson<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background_color: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animation_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    youtube_url: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct TraitJson<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_type: Option<&'a str>,
+    trait_type: &'a str,
+    value: &'a str,
+}
+
+impl<'a> From<&'a Metadata> for MetadataJson<'a> {
+    fn from(meta: &'a Metadata) -> Self {
+        MetadataJson {
+            name: meta.name.as_deref(),
+            description: meta.description.as_deref(),
+            image: meta.image.as_deref(),
+            image_data: meta.image_data.as_deref(),
+            external_url: meta.external_url.as_deref(),
+            attributes: meta
+                .attributes
+                .as_ref()
+                .map(|attrs| attrs.iter().map(TraitJson::from).collect()),
+            background_color: meta.background_color.as_deref(),
+            animation_url: meta.animation_url.as_deref(),
+            youtube_url: meta.youtube_url.as_deref(),
+        }
+    }
+}
+
+impl<'a> From<&'a Trait> for TraitJson<'a> {
+    fn from(attr: &'a Trait) -> Self {
+        TraitJson {
+            display_type: attr.display_type.as_deref(),
+            trait_type: &attr.trait_type,
+            value: &attr.value,
+        }
+    }
+}
+
+/// Serializes the metadata as an ERC721 Metadata JSON document
+pub fn render_metadata_json(meta: &Metadata) -> StdResult<String> {
+    let raw = to_vec(&MetadataJson::from(meta))?;
+    String::from_utf8(raw).map_err(StdError::invalid_utf8)
+}
+
+/// Wraps a rendered JSON document into a `data:` URI
+pub fn json_data_uri(json: &str) -> String {
+    format!(
+        "{}{}",
+        JSON_DATA_URI_PREFIX,
+        Binary::from(json.as_bytes()).to_base64()
+    )
+}
+
+pub fn query_nft_metadata_json(deps: Deps, token_id: String) -> StdResult<NftMetadataJsonResponse> {
+    let contract = Cw721MetadataContract::default();
+    let token_info = contract.tokens.load(deps.storage, &token_id)?;
+
+    let json = token_info
+        .extension
+        .as_ref()
+        .map(render_metadata_json)
+        .transpose()?;
+    let data_uri = json.as_deref().map(json_data_uri);
+
+    Ok(NftMetadataJsonResponse {
+        token_uri: token_info.token_uri,
+        json,
+        data_uri,
+    })
+}
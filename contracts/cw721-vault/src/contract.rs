@@ -0,0 +1,374 @@
+use crate::error::ContractError;
+use crate::msg::{
+    CollectionsResponse, DepositResponse, ExecuteMsg, InstantiateMsg, QueryMsg, VaultMsg,
+};
+use crate::state::{ADMIN, COLLECTIONS, DEPOSITS};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw721::{Cw721ExecuteMsg, Cw721Receiver, ReceivedNft};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw721-vault";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Accepts deposits from the allowed collections
+pub struct Vault;
+
+impl Cw721Receiver for Vault {
+    type Msg = VaultMsg;
+    type Error = ContractError;
+
+    fn is_collection_allowed(&self, deps: Deps, collection: &Addr) -> StdResult<bool> {
+        Ok(COLLECTIONS.contains(deps.storage, collection))
+    }
+
+    fn receive_nft(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        received: ReceivedNft<VaultMsg>,
+    ) -> Result<Response, ContractError> {
+        let VaultMsg::Deposit { beneficiary } = received.msg;
+        let beneficiary = deps
+            .api
+            .addr_validate(beneficiary.as_deref().unwrap_or(&received.sender))?;
+        DEPOSITS.save(
+            deps.storage,
+            (&received.collection, &received.token_id),
+            &beneficiary,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "deposit")
+            .add_attribute("collection", received.collection)
+            .add_attribute("token_id", received.token_id)
+            .add_attribute("beneficiary", beneficiary))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    ADMIN.save(deps.storage, &info.sender)?;
+    for collection in msg.collections {
+        let collection = deps.api.addr_validate(&collection)?;
+        COLLECTIONS.add(deps.storage, &collection)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("admin", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ReceiveNft(msg) => Vault.handle_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Withdraw {
+            collection,
+            token_id,
+        } => execute_withdraw(deps, info, collection, token_id),
+        ExecuteMsg::AddCollection { collection } => execute_add_collection(deps, info, collection),
+        ExecuteMsg::RemoveCollection { collection } => {
+            execute_remove_collection(deps, info, collection)
+        }
+    }
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let beneficiary = DEPOSITS.load(deps.storage, (&collection, &token_id))?;
+    if beneficiary != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    DEPOSITS.remove(deps.storage, (&collection, &token_id));
+
+    let transfer = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+            recipient: beneficiary.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("action", "withdraw")
+        .add_attribute("collection", collection)
+        .add_attribute("token_id", token_id)
+        .add_attribute("beneficiary", beneficiary))
+}
+
+pub fn execute_add_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+) -> Result<Response, ContractError> {
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTIONS.add(deps.storage, &collection)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_collection")
+        .add_attribute("collection", collection))
+}
+
+pub fn execute_remove_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: String,
+) -> Result<Response, ContractError> {
+    if ADMIN.load(deps.storage)? != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let collection = deps.api.addr_validate(&collection)?;
+    COLLECTIONS.remove(deps.storage, &collection);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_collection")
+        .add_attribute("collection", collection))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Collections { start_after, limit } => {
+            to_binary(&query_collections(deps, start_after, limit)?)
+        }
+        QueryMsg::Deposit {
+            collection,
+            token_id,
+        } => to_binary(&query_deposit(deps, collection, token_id)?),
+    }
+}
+
+fn query_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CollectionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let collections = COLLECTIONS
+        .list(deps.storage, start_after.as_ref(), limit)?
+        .into_iter()
+        .map(String::from)
+        .collect();
+    Ok(CollectionsResponse { collections })
+}
+
+fn query_deposit(deps: Deps, collection: String, token_id: String) -> StdResult<DepositResponse> {
+    let collection = deps.api.addr_validate(&collection)?;
+    let beneficiary = DEPOSITS
+        .may_load(deps.storage, (&collection, &token_id))?
+        .map(String::from);
+    Ok(DepositResponse { beneficiary })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, CosmosMsg, StdError};
+    use cw721::Cw721ReceiveMsg;
+
+    const ADMIN_ADDR: &str = "admin";
+    const COLLECTION: &str = "collection";
+
+    fn setup(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            collections: vec![COLLECTION.to_string()],
+        };
+        instantiate(deps, mock_env(), mock_info(ADMIN_ADDR, &[]), msg).unwrap();
+    }
+
+    fn deposit(beneficiary: Option<String>) -> ExecuteMsg {
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "1".to_string(),
+            msg: to_binary(&VaultMsg::Deposit { beneficiary }).unwrap(),
+        })
+    }
+
+    fn beneficiary(deps: Deps) -> Option<String> {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::Deposit {
+                collection: COLLECTION.to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+        from_binary::<DepositResponse>(&res).unwrap().beneficiary
+    }
+
+    #[test]
+    fn deposit_from_allowed_collection() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(COLLECTION, &[]),
+            deposit(None),
+        )
+        .unwrap();
+        assert_eq!(beneficiary(deps.as_ref()), Some("alice".to_string()));
+
+        // the sender can deposit on behalf of someone else
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(COLLECTION, &[]),
+            deposit(Some("bob".to_string())),
+        )
+        .unwrap();
+        assert_eq!(beneficiary(deps.as_ref()), Some("bob".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_collection() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        // anyone can call ReceiveNft directly, pretending to be a collection
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            deposit(None),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::generic_err("Collection alice is not allowed"))
+        );
+
+        // a removed collection is rejected too
+        let msg = ExecuteMsg::RemoveCollection {
+            collection: COLLECTION.to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN_ADDR, &[]), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(COLLECTION, &[]),
+            deposit(None),
+        )
+        .unwrap_err();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Collections {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let res: CollectionsResponse = from_binary(&res).unwrap();
+        assert!(res.collections.is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_payload() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "1".to_string(),
+            msg: to_binary("not a vault msg").unwrap(),
+        });
+        let err = execute(deps.as_mut(), mock_env(), mock_info(COLLECTION, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::ParseErr { .. })));
+        assert_eq!(beneficiary(deps.as_ref()), None);
+    }
+
+    #[test]
+    fn withdraw() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(COLLECTION, &[]),
+            deposit(Some("bob".to_string())),
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::Withdraw {
+            collection: COLLECTION.to_string(),
+            token_id: "1".to_string(),
+        };
+        // only the beneficiary can withdraw
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: COLLECTION.to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: "bob".to_string(),
+                    token_id: "1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(beneficiary(deps.as_ref()), None);
+
+        // the token is gone
+        execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), msg).unwrap_err();
+    }
+}
@@ -0,0 +1,60 @@
+use cw721::Cw721ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// cw721 contracts whose tokens can be deposited
+    pub collections: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Deposits a token sent with `SendNft`, the `msg` must be a `VaultMsg`
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Sends a deposited token back to its beneficiary, who must be the sender
+    Withdraw {
+        collection: String,
+        token_id: String,
+    },
+    /// Accepts tokens of another collection. Can only be called by the admin
+    AddCollection { collection: String },
+    /// Stops accepting tokens of a collection, deposited ones can still be withdrawn.
+    /// Can only be called by the admin
+    RemoveCollection { collection: String },
+}
+
+/// Payload of `SendNft` when depositing into the vault
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultMsg {
+    /// Keeps the token for the beneficiary, or for the sender if unset
+    Deposit { beneficiary: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Return type: `CollectionsResponse`
+    Collections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return type: `DepositResponse`
+    Deposit {
+        collection: String,
+        token_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionsResponse {
+    pub collections: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositResponse {
+    /// Who can withdraw the token, unset if it is not in the vault
+    pub beneficiary: Option<String>,
+}
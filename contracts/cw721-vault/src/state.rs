@@ -0,0 +1,9 @@
+use cosmwasm_std::Addr;
+use cw721::CollectionAllowlist;
+use cw_storage_plus::{Item, Map};
+
+/// Can change the allowed collections
+pub const ADMIN: Item<Addr> = Item::new("admin");
+pub const COLLECTIONS: CollectionAllowlist = CollectionAllowlist::new("collections");
+/// Who can withdraw each deposited token, stored as (collection, token_id)
+pub const DEPOSITS: Map<(&Addr, &str), Addr> = Map::new("deposits");
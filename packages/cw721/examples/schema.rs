@@ -5,8 +5,9 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 use cosmwasm_std::Empty;
 
 use cw721::{
-    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, Cw721ExecuteMsg,
-    Cw721QueryMsg, Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse, OperatorsResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, BalanceResponse,
+    CheckTransferResponse, ContractInfoResponse, Cw721ExecuteMsg, Cw721HookMsg, Cw721QueryMsg,
+    Cw721ReceiveMsg, Cw721TransferCheck, NftInfoResponse, NumTokensResponse, OperatorsResponse,
     OwnerOfResponse, TokensResponse,
 };
 
@@ -21,6 +22,9 @@ fn main() {
     export_schema(&schema_for!(Cw721ExecuteMsg), &out_dir);
     export_schema(&schema_for!(Cw721QueryMsg), &out_dir);
     export_schema(&schema_for!(Cw721ReceiveMsg), &out_dir);
+    export_schema(&schema_for!(Cw721TransferCheck), &out_dir);
+    export_schema(&schema_for!(CheckTransferResponse), &out_dir);
+    export_schema(&schema_for!(Cw721HookMsg), &out_dir);
     export_schema_with_title(
         &schema_for!(AllNftInfoResponse<Extension>),
         &out_dir,
@@ -37,5 +41,6 @@ fn main() {
         "NftInfoResponse",
     );
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
+    export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
 }
@@ -0,0 +1,205 @@
+use cosmwasm_std::Event;
+
+/// Version of the event layout below, emitted with every event so indexers can tell
+/// layouts apart if it ever changes
+pub const CW721_EVENT_VERSION: &str = "1";
+
+/// Cw721Event describes a state change of a collection in a uniform way.
+/// It is emitted as a `wasm-cw721_<action>` event, eg. `wasm-cw721_transfer`,
+/// with the `version`, `sender`, `from`, `to`, `token_id` and `token_uri` attributes.
+/// Attributes that do not apply to an action, like `from` on mint, are left out
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cw721Event {
+    /// One of mint, transfer, send, burn, approve, revoke, approve_all or revoke_all
+    pub action: &'static str,
+    /// Who executed the action
+    pub sender: String,
+    /// Previous owner of the token, or granter of an approval
+    pub from: Option<String>,
+    /// New owner of the token, or grantee of an approval
+    pub to: Option<String>,
+    pub token_id: Option<String>,
+    pub token_uri: Option<String>,
+}
+
+impl Cw721Event {
+    fn new(action: &'static str, sender: impl Into<String>) -> Self {
+        Cw721Event {
+            action,
+            sender: sender.into(),
+            from: None,
+            to: None,
+            token_id: None,
+            token_uri: None,
+        }
+    }
+
+    fn token(mut self, token_id: impl Into<String>, token_uri: Option<String>) -> Self {
+        self.token_id = Some(token_id.into());
+        self.token_uri = token_uri;
+        self
+    }
+
+    fn parties(mut self, from: Option<String>, to: Option<String>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    pub fn mint(
+        sender: impl Into<String>,
+        owner: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("mint", sender)
+            .parties(None, Some(owner.into()))
+            .token(token_id, token_uri)
+    }
+
+    pub fn transfer(
+        sender: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("transfer", sender)
+            .parties(Some(from.into()), Some(to.into()))
+            .token(token_id, token_uri)
+    }
+
+    /// A transfer to a contract, which is notified with a `Cw721ReceiveMsg`
+    pub fn send(
+        sender: impl Into<String>,
+        from: impl Into<String>,
+        contract: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("send", sender)
+            .parties(Some(from.into()), Some(contract.into()))
+            .token(token_id, token_uri)
+    }
+
+    pub fn burn(
+        sender: impl Into<String>,
+        owner: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("burn", sender)
+            .parties(Some(owner.into()), None)
+            .token(token_id, token_uri)
+    }
+
+    pub fn approve(
+        sender: impl Into<String>,
+        owner: impl Into<String>,
+        spender: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("approve", sender)
+            .parties(Some(owner.into()), Some(spender.into()))
+            .token(token_id, token_uri)
+    }
+
+    pub fn revoke(
+        sender: impl Into<String>,
+        owner: impl Into<String>,
+        spender: impl Into<String>,
+        token_id: impl Into<String>,
+        token_uri: Option<String>,
+    ) -> Self {
+        Self::new("revoke", sender)
+            .parties(Some(owner.into()), Some(spender.into()))
+            .token(token_id, token_uri)
+    }
+
+    /// Operators are granted by the sender on all of their tokens
+    pub fn approve_all(sender: impl Into<String>, operator: impl Into<String>) -> Self {
+        let sender = sender.into();
+        Self::new("approve_all", sender.clone()).parties(Some(sender), Some(operator.into()))
+    }
+
+    pub fn revoke_all(sender: impl Into<String>, operator: impl Into<String>) -> Self {
+        let sender = sender.into();
+        Self::new("revoke_all", sender.clone()).parties(Some(sender), Some(operator.into()))
+    }
+}
+
+impl From<Cw721Event> for Event {
+    fn from(event: Cw721Event) -> Event {
+        let optional = vec![
+            ("from", event.from),
+            ("to", event.to),
+            ("token_id", event.token_id),
+            ("token_uri", event.token_uri),
+        ];
+        // the chain prefixes custom events with "wasm-"
+        Event::new(format!("cw721_{}", event.action))
+            .add_attribute("version", CW721_EVENT_VERSION)
+            .add_attribute("sender", event.sender)
+            .add_attributes(
+                optional
+                    .into_iter()
+                    .filter_map(|(key, value)| value.map(|v| (key, v))),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_events() {
+        let event: Event = Cw721Event::transfer("bob", "alice", "carol", "1", None).into();
+        assert_eq!(
+            event,
+            Event::new("cw721_transfer")
+                .add_attribute("version", CW721_EVENT_VERSION)
+                .add_attribute("sender", "bob")
+                .add_attribute("from", "alice")
+                .add_attribute("to", "carol")
+                .add_attribute("token_id", "1")
+        );
+
+        // attributes that do not apply are left out
+        let event: Event =
+            Cw721Event::mint("minter", "alice", "1", Some(String::from("ipfs://1"))).into();
+        assert_eq!(
+            event,
+            Event::new("cw721_mint")
+                .add_attribute("version", CW721_EVENT_VERSION)
+                .add_attribute("sender", "minter")
+                .add_attribute("to", "alice")
+                .add_attribute("token_id", "1")
+                .add_attribute("token_uri", "ipfs://1")
+        );
+        let event: Event = Cw721Event::burn("alice", "alice", "1", None).into();
+        assert_eq!(
+            event,
+            Event::new("cw721_burn")
+                .add_attribute("version", CW721_EVENT_VERSION)
+                .add_attribute("sender", "alice")
+                .add_attribute("from", "alice")
+                .add_attribute("token_id", "1")
+        );
+    }
+
+    #[test]
+    fn operator_events() {
+        // the sender grants on their own tokens
+        let event: Event = Cw721Event::approve_all("alice", "market").into();
+        assert_eq!(
+            event,
+            Event::new("cw721_approve_all")
+                .add_attribute("version", CW721_EVENT_VERSION)
+                .add_attribute("sender", "alice")
+                .add_attribute("from", "alice")
+                .add_attribute("to", "market")
+        );
+    }
+}
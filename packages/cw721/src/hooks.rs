@@ -0,0 +1,108 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, CustomQuery, QueryRequest, StdResult, WasmMsg, WasmQuery,
+};
+
+/// Cw721TransferCheck is sent as a query to every pre-transfer hook registered on a collection,
+/// before a token changes hands. It should be de/serialized under `CheckTransfer()` variant
+/// in a QueryMsg
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw721TransferCheck {
+    /// Current owner, unset when the token is being minted
+    pub from: Option<String>,
+    /// New owner, unset when the token is being burned
+    pub to: Option<String>,
+    pub token_id: String,
+}
+
+impl Cw721TransferCheck {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = TransferHookQueryMsg::CheckTransfer(self);
+        to_binary(&msg)
+    }
+
+    /// creates a query_request sending this struct to the named contract
+    pub fn into_query_request<T: Into<String>, Q: CustomQuery>(
+        self,
+        contract_addr: T,
+    ) -> StdResult<QueryRequest<Q>> {
+        let msg = self.into_binary()?;
+        Ok(WasmQuery::Smart {
+            contract_addr: contract_addr.into(),
+            msg,
+        }
+        .into())
+    }
+}
+
+/// Returned by a pre-transfer hook. If not allowed, the whole transfer is rejected
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CheckTransferResponse {
+    pub allowed: bool,
+    /// Why the transfer was rejected, if it was
+    pub reason: Option<String>,
+}
+
+/// This is just a helper to properly serialize the above message.
+/// The actual hook should include this variant in the larger QueryMsg enum
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+enum TransferHookQueryMsg {
+    CheckTransfer(Cw721TransferCheck),
+}
+
+/// Cw721HookMsg is sent to every subscriber registered on a collection, after a token
+/// was minted, changed hands or was burned. It should be de/serialized under `Cw721Hook()`
+/// variant in a ExecuteMsg
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721HookMsg {
+    Minted {
+        owner: String,
+        token_id: String,
+    },
+    /// Sent for both transfers and sends
+    Transferred {
+        from: String,
+        to: String,
+        token_id: String,
+    },
+    Burned {
+        owner: String,
+        token_id: String,
+    },
+}
+
+impl Cw721HookMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = HookExecuteMsg::Cw721Hook(self);
+        to_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>, C>(self, contract_addr: T) -> StdResult<CosmosMsg<C>>
+    where
+        C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+/// This is just a helper to properly serialize the above message.
+/// The actual subscriber should include this variant in the larger ExecuteMsg enum
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+enum HookExecuteMsg {
+    Cw721Hook(Cw721HookMsg),
+}
@@ -1,3 +1,5 @@
+mod event;
+mod hooks;
 mod msg;
 mod query;
 mod receiver;
@@ -5,11 +7,13 @@ mod traits;
 
 pub use cw_utils::Expiration;
 
+pub use crate::event::{Cw721Event, CW721_EVENT_VERSION};
+pub use crate::hooks::{CheckTransferResponse, Cw721HookMsg, Cw721TransferCheck};
 pub use crate::msg::Cw721ExecuteMsg;
 pub use crate::query::{
-    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse,
-    Cw721QueryMsg, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
-    TokensResponse,
+    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, BalanceResponse,
+    ContractInfoResponse, Cw721QueryMsg, NftInfoResponse, NumTokensResponse, OperatorsResponse,
+    OwnerOfResponse, TokensResponse,
 };
-pub use crate::receiver::Cw721ReceiveMsg;
+pub use crate::receiver::{CollectionAllowlist, Cw721ReceiveMsg, Cw721Receiver, ReceivedNft};
 pub use crate::traits::{CustomMsg, Cw721, Cw721Execute, Cw721Query};
@@ -29,6 +29,9 @@ pub enum Cw721QueryMsg {
     },
     /// Total number of tokens issued
     NumTokens {},
+    /// Number of tokens held by the given address.
+    /// Return type: `BalanceResponse`
+    Balance { owner: String },
 
     /// With MetaData Extension.
     /// Returns top-level metadata about the contract: `ContractInfoResponse`
@@ -99,6 +102,11 @@ pub struct NumTokensResponse {
     pub count: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct BalanceResponse {
+    pub balance: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct ContractInfoResponse {
     pub name: String,
@@ -1,7 +1,14 @@
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{to_binary, Binary, CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdError, StdResult, Storage, WasmMsg,
+};
+use cw_storage_plus::{Bound, Map};
+
+use crate::traits::CustomMsg;
 
 /// Cw721ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -21,6 +28,18 @@ impl Cw721ReceiveMsg {
 
     /// creates a cosmos_msg sending this struct to the named contract
     pub fn into_cosmos_msg<T: Into<String>, C>(self, contract_addr: T) -> StdResult<CosmosMsg<C>>
+    where
+        C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        self.into_cosmos_msg_with_funds(contract_addr, vec![])
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract, along with the given funds
+    pub fn into_cosmos_msg_with_funds<T: Into<String>, C>(
+        self,
+        contract_addr: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg<C>>
     where
         C: Clone + std::fmt::Debug + PartialEq + JsonSchema,
     {
@@ -28,10 +47,15 @@ impl Cw721ReceiveMsg {
         let execute = WasmMsg::Execute {
             contract_addr: contract_addr.into(),
             msg,
-            funds: vec![],
+            funds,
         };
         Ok(execute.into())
     }
+
+    /// decodes the payload the sender attached to `SendNft`
+    pub fn decode_msg<T: DeserializeOwned>(&self) -> StdResult<T> {
+        from_binary(&self.msg)
+    }
 }
 
 /// This is just a helper to properly serialize the above message.
@@ -41,3 +65,234 @@ impl Cw721ReceiveMsg {
 enum ReceiverExecuteMsg {
     ReceiveNft(Cw721ReceiveMsg),
 }
+
+/// A token received through `SendNft`, with the payload decoded
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceivedNft<T> {
+    /// The cw721 contract the token belongs to, authenticated as the caller
+    pub collection: Addr,
+    /// Who sent the token, ie. its previous owner or an approved spender
+    pub sender: String,
+    pub token_id: String,
+    pub msg: T,
+}
+
+/// Cw721Receiver is implemented by contracts that accept tokens through `SendNft`.
+/// The `ReceiveNft` variant of the execute entry point hands the message to `handle_receive_nft`
+pub trait Cw721Receiver<C = Empty>
+where
+    C: CustomMsg,
+{
+    /// The payload senders attach to `SendNft`
+    type Msg: DeserializeOwned;
+    type Error: From<StdError>;
+
+    /// returns true if tokens of the given collection are accepted
+    fn is_collection_allowed(&self, deps: Deps, collection: &Addr) -> StdResult<bool>;
+
+    /// Accepts a token sent by an allowed collection. Returning an error rejects it:
+    /// the whole send is reverted and the token stays with its owner
+    fn receive_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        received: ReceivedNft<Self::Msg>,
+    ) -> Result<Response<C>, Self::Error>;
+
+    /// authenticates the calling collection and decodes the payload before `receive_nft`
+    fn handle_receive_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response<C>, Self::Error> {
+        if !self.is_collection_allowed(deps.as_ref(), &info.sender)? {
+            return Err(StdError::generic_err(format!(
+                "Collection {} is not allowed",
+                info.sender
+            ))
+            .into());
+        }
+        let received = ReceivedNft {
+            msg: msg.decode_msg()?,
+            collection: info.sender,
+            sender: msg.sender,
+            token_id: msg.token_id,
+        };
+        self.receive_nft(deps, env, received)
+    }
+}
+
+/// Set of collections a receiver accepts tokens from, kept in the receiver's storage
+pub struct CollectionAllowlist<'a>(Map<'a, &'a Addr, Empty>);
+
+impl<'a> CollectionAllowlist<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        CollectionAllowlist(Map::new(namespace))
+    }
+
+    pub fn add(&self, storage: &mut dyn Storage, collection: &Addr) -> StdResult<()> {
+        self.0.save(storage, collection, &Empty {})
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, collection: &Addr) {
+        self.0.remove(storage, collection)
+    }
+
+    pub fn contains(&self, storage: &dyn Storage, collection: &Addr) -> bool {
+        self.0.has(storage, collection)
+    }
+
+    /// lists the allowed collections in address order
+    pub fn list(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<&Addr>,
+        limit: usize,
+    ) -> StdResult<Vec<Addr>> {
+        self.0
+            .keys(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::coins;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    const ALLOWLIST: CollectionAllowlist = CollectionAllowlist::new("collections");
+
+    /// Accepts tokens of allowed collections, with a note as payload
+    struct NoteReceiver;
+
+    impl Cw721Receiver for NoteReceiver {
+        type Msg = String;
+        type Error = StdError;
+
+        fn is_collection_allowed(&self, deps: Deps, collection: &Addr) -> StdResult<bool> {
+            Ok(ALLOWLIST.contains(deps.storage, collection))
+        }
+
+        fn receive_nft(
+            &self,
+            _deps: DepsMut,
+            _env: Env,
+            received: ReceivedNft<String>,
+        ) -> StdResult<Response> {
+            Ok(Response::new()
+                .add_attribute("collection", received.collection)
+                .add_attribute("sender", received.sender)
+                .add_attribute("token_id", received.token_id)
+                .add_attribute("note", received.msg))
+        }
+    }
+
+    fn receive_msg(msg: Binary) -> Cw721ReceiveMsg {
+        Cw721ReceiveMsg {
+            sender: String::from("alice"),
+            token_id: String::from("1"),
+            msg,
+        }
+    }
+
+    #[test]
+    fn allowlist() {
+        let mut deps = mock_dependencies();
+        let (punks, apes) = (Addr::unchecked("punks"), Addr::unchecked("apes"));
+        ALLOWLIST.add(&mut deps.storage, &punks).unwrap();
+        ALLOWLIST.add(&mut deps.storage, &apes).unwrap();
+        assert!(ALLOWLIST.contains(&deps.storage, &punks));
+        assert_eq!(
+            ALLOWLIST.list(&deps.storage, None, 10).unwrap(),
+            vec![apes.clone(), punks.clone()]
+        );
+        assert_eq!(
+            ALLOWLIST.list(&deps.storage, Some(&apes), 10).unwrap(),
+            vec![punks.clone()]
+        );
+
+        ALLOWLIST.remove(&mut deps.storage, &punks);
+        assert!(!ALLOWLIST.contains(&deps.storage, &punks));
+        assert_eq!(ALLOWLIST.list(&deps.storage, None, 10).unwrap(), vec![apes]);
+    }
+
+    #[test]
+    fn handle_receive_nft() {
+        let mut deps = mock_dependencies();
+        ALLOWLIST
+            .add(&mut deps.storage, &Addr::unchecked("punks"))
+            .unwrap();
+        let msg = receive_msg(to_binary("hodl").unwrap());
+
+        // the calling contract is the collection
+        let res = NoteReceiver
+            .handle_receive_nft(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("punks", &[]),
+                msg.clone(),
+            )
+            .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_attribute("collection", "punks")
+                .add_attribute("sender", "alice")
+                .add_attribute("token_id", "1")
+                .add_attribute("note", "hodl")
+        );
+
+        // anyone else is rejected, whatever sender the message claims
+        let err = NoteReceiver
+            .handle_receive_nft(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Collection alice is not allowed")
+        );
+
+        // so are payloads of the wrong type
+        let msg = receive_msg(to_binary(&42).unwrap());
+        let err = NoteReceiver
+            .handle_receive_nft(deps.as_mut(), mock_env(), mock_info("punks", &[]), msg)
+            .unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+    }
+
+    #[test]
+    fn into_cosmos_msg() {
+        let msg = receive_msg(to_binary("hodl").unwrap());
+        let expected_msg = to_binary(&ReceiverExecuteMsg::ReceiveNft(msg.clone())).unwrap();
+
+        let res: CosmosMsg = msg.clone().into_cosmos_msg("vault").unwrap();
+        assert_eq!(
+            res,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("vault"),
+                msg: expected_msg.clone(),
+                funds: vec![],
+            })
+        );
+
+        let res: CosmosMsg = msg
+            .into_cosmos_msg_with_funds("vault", coins(100, "ustars"))
+            .unwrap();
+        assert_eq!(
+            res,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("vault"),
+                msg: expected_msg,
+                funds: coins(100, "ustars"),
+            })
+        );
+    }
+}
@@ -4,7 +4,7 @@ use serde::Serialize;
 
 use crate::query::ApprovalResponse;
 use crate::{
-    AllNftInfoResponse, ApprovalsResponse, ContractInfoResponse, NftInfoResponse,
+    AllNftInfoResponse, ApprovalsResponse, BalanceResponse, ContractInfoResponse, NftInfoResponse,
     NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
 use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
@@ -104,6 +104,8 @@ where
 
     fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse>;
 
+    fn balance(&self, deps: Deps, owner: String) -> StdResult<BalanceResponse>;
+
     fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>>;
 
     fn owner_of(